@@ -1,12 +1,120 @@
 use aoc_2020::day_03::*;
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// The map as it was before rows were bit-packed, one `Tile` per square, kept here to compare
+// against.
+struct TileMap {
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+}
+
+impl TileMap {
+    fn parse(input: &str) -> Self {
+        Self {
+            width: input.find('\n').unwrap(),
+            height: input.lines().count(),
+            tiles: input.chars().filter_map(Tile::from_char).collect(),
+        }
+    }
+
+    fn tile(&self, x: usize, y: usize) -> Tile {
+        assert!(y < self.height);
+
+        let trunc_x = x % self.width;
+        let i = y * self.width + trunc_x;
+
+        self.tiles[i]
+    }
+
+    fn trees_on_slope(&self, delta_x: usize, delta_y: usize) -> u64 {
+        let mut x = 0;
+        let mut y = 0;
+        let mut trees_encountered = 0;
+
+        while y < self.height {
+            if self.tile(x, y) == Tile::Tree {
+                trees_encountered += 1;
+            }
+
+            x += delta_x;
+            y += delta_y;
+        }
+
+        trees_encountered
+    }
+}
+
+// A `width` by `height` map with roughly one tree in five squares, always the same for a size.
+fn generate_map(width: usize, height: usize) -> String {
+    let mut state: u64 = 0x2020;
+    let mut map = String::with_capacity((width + 1) * height);
+
+    for _ in 0..height {
+        for _ in 0..width {
+            // Knuth's MMIX linear congruential generator.
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            map.push(if (state >> 33).is_multiple_of(5) {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        map.push('\n');
+    }
+
+    map
+}
 
 pub fn benchmark(c: &mut Criterion) {
     let input = std::fs::read_to_string("input/day-03.txt").expect("Couldn't read input file");
 
     c.bench_function("Day 03 Part 1", |b| b.iter(|| part1(&input)));
     c.bench_function("Day 03 Part 2", |b| b.iter(|| part2(&input)));
+
+    let slopes: Vec<Slope> = (1..=8)
+        .flat_map(|dx| (1..=4).map(move |dy| Slope { dx, dy }))
+        .collect();
+
+    let mut group = c.benchmark_group("Day 03 Generated Maps");
+    for (width, height) in [(31, 100_000), (1000, 10_000), (4096, 50_000)].iter() {
+        let input = generate_map(*width, *height);
+        let tile_map = TileMap::parse(&input);
+        let map = Map::parse(&input);
+        let size = format!("{}x{}", width, height);
+
+        group.bench_with_input(
+            BenchmarkId::new("Tile per square", &size),
+            &slopes,
+            |b, slopes| {
+                b.iter(|| {
+                    slopes
+                        .iter()
+                        .map(|slope| tile_map.trees_on_slope(slope.dx, slope.dy))
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Bitset per row", &size),
+            &slopes,
+            |b, slopes| {
+                b.iter(|| {
+                    slopes
+                        .iter()
+                        .map(|slope| map.trees_on_slope(slope.dx, slope.dy))
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("Batched", &size), &slopes, |b, slopes| {
+            b.iter(|| map.trees_on_slopes(slopes))
+        });
+    }
+    group.finish();
 }
 
 criterion_group!(benches, benchmark);
@@ -0,0 +1,60 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "aoc_2020.h"
+
+static char *read_file(const char *path, size_t *len) {
+    FILE *file = fopen(path, "rb");
+    if (!file) {
+        return NULL;
+    }
+
+    fseek(file, 0, SEEK_END);
+    *len = (size_t)ftell(file);
+    rewind(file);
+
+    char *buf = malloc(*len);
+    if (buf && fread(buf, 1, *len, file) != *len) {
+        free(buf);
+        buf = NULL;
+    }
+
+    fclose(file);
+    return buf;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 3) {
+        fprintf(stderr, "usage: %s <day> <input file>\n", argv[0]);
+        return 2;
+    }
+
+    uint32_t day = (uint32_t)strtoul(argv[1], NULL, 10);
+    size_t len = 0;
+    char *input = read_file(argv[2], &len);
+    if (!input) {
+        fprintf(stderr, "Couldn't read input file\n");
+        return 2;
+    }
+
+    for (uint32_t part = 1; part <= 2; part++) {
+        AocAnswer answer = aoc_solve(day, part, (const uint8_t *)input, len);
+
+        switch (answer.status) {
+        case AOC_STATUS_OK:
+            printf("Part %u: %lld\n", part, (long long)answer.value);
+            break;
+        case AOC_STATUS_NO_SOLUTION:
+            printf("No solution for part %u\n", part);
+            break;
+        default:
+            printf("Part %u failed (%d): %s\n", part, answer.status, answer.message);
+            break;
+        }
+
+        aoc_string_free(answer.message);
+    }
+
+    free(input);
+    return 0;
+}
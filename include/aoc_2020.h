@@ -0,0 +1,84 @@
+/*
+ * Advent of Code 2020 solvers.
+ *
+ * Ownership: every non-null `message` in an `AocAnswer` is allocated by this library and must be
+ * released exactly once with `aoc_string_free`. Input buffers are only borrowed for the duration
+ * of the call.
+ */
+
+#ifndef AOC_2020_H
+#define AOC_2020_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum AocStatus {
+  /**
+   * `value` holds the answer.
+   */
+  AOC_STATUS_OK,
+  /**
+   * The solver ran to completion but found no answer for this input.
+   */
+  AOC_STATUS_NO_SOLUTION,
+  AOC_STATUS_UNKNOWN_DAY,
+  AOC_STATUS_UNKNOWN_PART,
+  /**
+   * The input pointer was null or the buffer was not valid UTF-8.
+   */
+  AOC_STATUS_INVALID_INPUT,
+  /**
+   * The part exists but has not been written yet.
+   */
+  AOC_STATUS_UNIMPLEMENTED,
+  /**
+   * The solver panicked, usually because the input was malformed. See `message`.
+   */
+  AOC_STATUS_PANICKED,
+} AocStatus;
+
+typedef struct AocAnswer {
+  enum AocStatus status;
+  /**
+   * Only meaningful when `status` is `AOC_STATUS_OK`.
+   */
+  int64_t value;
+  /**
+   * A human-readable description of the error, or null. Free with `aoc_string_free`.
+   */
+  char *message;
+} AocAnswer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solves `part` (1 or 2) of `day` using the `len` bytes at `input` as the puzzle input.
+ *
+ * # Safety
+ *
+ * `input` must either be null or point to at least `len` readable bytes.
+ */
+struct AocAnswer aoc_solve(uint32_t day, uint32_t part, const uint8_t *input, size_t len);
+
+/**
+ * Releases a string returned by this library. Passing null is a no-op.
+ *
+ * # Safety
+ *
+ * `s` must be null or a pointer previously returned by this library that has not already been
+ * freed.
+ */
+void aoc_string_free(char *s);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* AOC_2020_H */
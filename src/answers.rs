@@ -0,0 +1,56 @@
+use std::{collections::HashMap, io, path::Path};
+
+use itertools::Itertools;
+
+use crate::registry::Answer;
+
+pub const DEFAULT_PATH: &str = "input/answers.txt";
+
+/// Known-good answers keyed by `(day, part)`.
+#[derive(Debug, Default)]
+pub struct KnownAnswers(HashMap<(u32, u32), i64>);
+
+impl KnownAnswers {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut answers = HashMap::new();
+
+        for (line_number, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (day, part, answer) = parse_line(line).ok_or_else(|| {
+                format!(
+                    "line {}: expected `<day> <part> <answer>`, found {:?}",
+                    line_number + 1,
+                    line
+                )
+            })?;
+
+            answers.insert((day, part), answer);
+        }
+
+        Ok(Self(answers))
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<i64> {
+        self.0.get(&(day, part)).copied()
+    }
+
+    /// `None` if there is no known answer to compare against.
+    pub fn matches(&self, day: u32, part: u32, answer: Answer) -> Option<bool> {
+        let known = self.get(day, part)?;
+        Some(answer == Answer::Number(known))
+    }
+}
+
+fn parse_line(line: &str) -> Option<(u32, u32, i64)> {
+    let (day, part, answer) = line.split_whitespace().collect_tuple()?;
+    Some((day.parse().ok()?, part.parse().ok()?, answer.parse().ok()?))
+}
@@ -0,0 +1,13 @@
+use std::time::Duration;
+
+use aoc_2020::{answers::*, dashboard::*};
+
+fn main() -> std::io::Result<()> {
+    let known_answers = KnownAnswers::load(DEFAULT_PATH).unwrap_or_default();
+
+    let mut terminal = ratatui::init();
+    let result = Dashboard::new(known_answers, Duration::from_secs(10)).run(&mut terminal);
+    ratatui::restore();
+
+    result
+}
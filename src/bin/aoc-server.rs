@@ -0,0 +1,16 @@
+use aoc_2020::server::*;
+
+#[tokio::main]
+async fn main() {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:3000".to_string());
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .expect("Couldn't bind to address");
+
+    println!("Listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, router(Config::default()))
+        .await
+        .unwrap();
+}
@@ -0,0 +1,133 @@
+use aoc_2020::{
+    answers::{self, KnownAnswers},
+    lint,
+    progress::{self, Date, Progress, Stage},
+    registry::{self, Answer},
+};
+
+const USAGE: &str = "\
+Usage:
+    aoc run <day> [input file]
+    aoc lint <day> [input file]
+    aoc stars";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let code = match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["run", day, rest @ ..] if rest.len() <= 1 => run_command(day, rest.first().copied()),
+        ["lint", day, rest @ ..] if rest.len() <= 1 => lint_command(day, rest.first().copied()),
+        ["stars"] => stars_command(),
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    };
+
+    std::process::exit(code);
+}
+
+fn parse_day(day: &str) -> &'static registry::Day {
+    day.parse().ok().and_then(registry::day).unwrap_or_else(|| {
+        eprintln!("No such day: {}", day);
+        std::process::exit(2);
+    })
+}
+
+fn load_known_answers() -> KnownAnswers {
+    KnownAnswers::load(answers::DEFAULT_PATH).unwrap_or_default()
+}
+
+fn load_progress() -> Progress {
+    Progress::load(progress::DEFAULT_PATH).expect("Couldn't load progress file")
+}
+
+fn run_command(day: &str, path: Option<&str>) -> i32 {
+    let day = parse_day(day);
+    let known_answers = load_known_answers();
+    let input_path = path.map_or_else(|| day.input_path(), Into::into);
+    let input = std::fs::read_to_string(&input_path).expect("Couldn't read input file");
+
+    // Progress is only about the real input, so runs against other files aren't recorded.
+    let mut progress = path.is_none().then(load_progress);
+    let mut code = 0;
+
+    for part in 1..=2 {
+        let result = registry::solve(day.number, part, &input);
+
+        match &result {
+            Ok(Answer::Number(n)) => println!("Part {}: {}", part, n),
+            Ok(Answer::NoSolution) => println!("No solution for part {}", part),
+            Err(e) => {
+                println!("Part {} failed: {}", part, e);
+                code = 1;
+            }
+        }
+
+        if let Some(progress) = &mut progress {
+            let stage = Stage::from_result(day.number, part, &result, &known_answers);
+            progress.record(day.number, part, stage, Date::today());
+        }
+    }
+
+    if let Some(progress) = progress {
+        progress
+            .save(progress::DEFAULT_PATH)
+            .expect("Couldn't save progress file");
+    }
+
+    code
+}
+
+fn lint_command(day: &str, path: Option<&str>) -> i32 {
+    let day = parse_day(day);
+    let path = path.map_or_else(|| day.input_path(), Into::into);
+    let input = std::fs::read_to_string(&path).expect("Couldn't read input file");
+
+    let problems = lint::lint(day.number, &input).expect("No grammar for this day");
+    for problem in &problems {
+        match problem.line {
+            Some(line) => println!("{}:{}: {}", path.display(), line, problem.message),
+            None => println!("{}: {}", path.display(), problem.message),
+        }
+    }
+
+    if problems.is_empty() {
+        println!("{}: no problems found", path.display());
+        0
+    } else {
+        1
+    }
+}
+
+fn stars_command() -> i32 {
+    let mut progress = load_progress();
+    progress.update(&load_known_answers(), Date::today());
+    progress
+        .save(progress::DEFAULT_PATH)
+        .expect("Couldn't save progress file");
+
+    print!("{}", progress.calendar());
+    println!();
+
+    for day in registry::DAYS {
+        for part in 1..=2 {
+            if let Some(record) = progress.get(day.number, part) {
+                match record.first_solved {
+                    Some(date) => println!(
+                        "Day {:>2} part {}: {:<13} first solved {}",
+                        day.number, part, record.stage, date
+                    ),
+                    None => println!("Day {:>2} part {}: {}", day.number, part, record.stage),
+                }
+            }
+        }
+    }
+
+    0
+}
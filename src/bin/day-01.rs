@@ -1,11 +1,11 @@
-use std::{collections::HashSet, io::prelude::*};
+use std::io::prelude::*;
 
 use aoc_2020::day_01::*;
 
 fn main() {
     let filename = std::env::args().nth(1).unwrap();
     let file = std::fs::File::open(filename).expect("Couldn't open input file");
-    let input: HashSet<i64> = std::io::BufReader::new(file)
+    let input: Vec<i64> = std::io::BufReader::new(file)
         .lines()
         .map(|line| line.unwrap().parse().unwrap())
         .collect();
@@ -15,10 +15,21 @@ fn main() {
     } else {
         println!("No solution for part 1");
     }
+    warn_if_ambiguous(&input, 1, 2);
 
     if let Some(solution) = part2(&input) {
         println!("Part 2: {}", solution);
     } else {
         println!("No solution for part 2");
     }
+    warn_if_ambiguous(&input, 2, 3);
+}
+
+fn warn_if_ambiguous(input: &[i64], part: u32, k: usize) {
+    if let Solutions::Ambiguous(count) = summarize_k_sums(input, k, TARGET) {
+        println!(
+            "Warning: {} combinations match for part {}, the answer above is only the first",
+            count, part
+        );
+    }
 }
@@ -3,13 +3,65 @@ use std::io::prelude::*;
 use aoc_2020::day_02::*;
 
 fn main() {
-    let filename = std::env::args().nth(1).unwrap();
+    let mut args = std::env::args().skip(1);
+    let filename = args.next().unwrap();
     let file = std::fs::File::open(filename).expect("Couldn't open input file");
     let input: Vec<String> = std::io::BufReader::new(file)
         .lines()
         .map(|line| line.unwrap())
         .collect();
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    let mut policy_path = None;
+    let mut mode = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--policy" => policy_path = Some(args.next().expect("--policy needs a file")),
+            "--report" | "--report-json" => mode = Some(arg),
+            _ => panic!(
+                "Unknown flag {:?}, expected --policy <file>, --report or --report-json",
+                arg
+            ),
+        }
+    }
+
+    // (heading, JSON key, policy)
+    let policies: Vec<(&str, &str, Box<dyn PasswordPolicy>)> = match policy_path {
+        Some(policy_path) => {
+            let source = std::fs::read_to_string(&policy_path).expect("Couldn't open policy file");
+            match policy_file::parse(&source) {
+                Ok(policy) => vec![("Policy", "policy", policy)],
+                Err(e) => {
+                    eprintln!("{}: {}", policy_path, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => vec![
+            ("Part 1", "part1", Box::new(CountInRange)),
+            ("Part 2", "part2", Box::new(PositionXor)),
+        ],
+    };
+
+    match mode.as_deref() {
+        None => {
+            for (heading, _, policy) in &policies {
+                println!("{}: {}", heading, count_valid(&input, policy.as_ref()));
+            }
+        }
+        Some("--report") => {
+            for (heading, _, policy) in &policies {
+                println!("{}:\n{}", heading, report(&input, policy.as_ref()));
+            }
+        }
+        Some(_) => {
+            let reports: serde_json::Map<String, serde_json::Value> = policies
+                .iter()
+                .map(|(_, key, policy)| {
+                    let report = report(&input, policy.as_ref());
+                    (key.to_string(), serde_json::to_value(report).unwrap())
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+        }
+    }
 }
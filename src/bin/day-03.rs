@@ -1,9 +1,77 @@
 use aoc_2020::day_03::*;
 
+const USAGE: &str = "usage: day-03 <input> [--edges wrap|bounded|mirrored|torus] \
+                     [search <max dx> <max dy> | render ansi|svg <dx>,<dy>...]";
+
 fn main() {
-    let filename = std::env::args().nth(1).unwrap();
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    // The edges only apply to `search` and `render`. The puzzle itself always wraps.
+    let mut edges = Edges::Wrap;
+    if let Some(i) = args.iter().position(|arg| arg == "--edges") {
+        edges = match args.get(i + 1).map(String::as_str) {
+            Some("wrap") => Edges::Wrap,
+            Some("bounded") => Edges::Bounded,
+            Some("mirrored") => Edges::Mirrored,
+            Some("torus") => Edges::Torus,
+            _ => panic!("{}", USAGE),
+        };
+        args.drain(i..i + 2);
+    }
+
+    let filename = args.first().expect(USAGE);
     let input = std::fs::read_to_string(filename).expect("Couldn't read input file");
+    let map = Map::parse(&input).with_edges(edges);
+
+    match args.get(1).map(String::as_str) {
+        None => {
+            println!("Part 1: {}", part1(&input));
+            println!("Part 2: {}", part2(&input));
+        }
+        Some("search") => {
+            let bound =
+                |i: usize| -> usize { args.get(i).and_then(|arg| arg.parse().ok()).expect(USAGE) };
+            let (max_dx, max_dy) = (bound(2), bound(3));
+
+            for (name, objective) in &[
+                ("Fewest", Objective::FewestTrees),
+                ("Most", Objective::MostTrees),
+            ] {
+                let ranked = map.rank_slopes(max_dx, max_dy, *objective);
+                if let Some(best) = ranked.first() {
+                    let ties = ranked
+                        .iter()
+                        .filter(|count| count.trees == best.trees)
+                        .count();
+                    println!(
+                        "{} trees: {} on right {}, down {} ({} slopes tied)",
+                        name, best.trees, best.slope.dx, best.slope.dy, ties
+                    );
+                }
+            }
+        }
+        Some("render") => {
+            let slopes: Vec<Slope> = args
+                .get(3..)
+                .unwrap_or_default()
+                .iter()
+                .map(|arg| {
+                    let (dx, dy) = arg.split_once(',').expect(USAGE);
+                    let slope = Slope {
+                        dx: dx.parse().expect(USAGE),
+                        dy: dy.parse().expect(USAGE),
+                    };
+                    assert!(slope.dy > 0, "A slope has to go down");
+                    slope
+                })
+                .collect();
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+            match args.get(2).map(String::as_str) {
+                Some("ansi") => print!("{}", render::ansi(&map, &slopes)),
+                Some("svg") => print!("{}", render::svg(&map, &slopes)),
+                _ => panic!("{}", USAGE),
+            }
+        }
+        Some(_) => panic!("{}", USAGE),
+    }
 }
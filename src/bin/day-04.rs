@@ -1,9 +1,50 @@
-use aoc_2020::day_04::*;
+use aoc_2020::{day_04::*, schema::Schema};
+
+const USAGE: &str = "usage: day-04 <input> [<schema.toml> | --report \
+                     [unknown|duplicate|malformed=invalid|ignore|error...]]";
 
 fn main() {
-    let input_file = std::env::args().nth(1).unwrap();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let input_file = args.first().expect(USAGE);
     let input = std::fs::read_to_string(input_file).unwrap();
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    match args.get(1).map(String::as_str) {
+        None => {
+            println!("Part 1: {}", part1(&input));
+            println!("Part 2: {}", part2(&input));
+        }
+        Some("--report") => {
+            let mut policy = Policy::default();
+            for setting in &args[2..] {
+                let (kind, treatment) = setting.split_once('=').expect(USAGE);
+                let treatment = match treatment {
+                    "invalid" => Treatment::Invalid,
+                    "ignore" => Treatment::Ignore,
+                    "error" => Treatment::Error,
+                    _ => panic!("{}", USAGE),
+                };
+                match kind {
+                    "unknown" => policy.unknown_field = treatment,
+                    "duplicate" => policy.duplicate_field = treatment,
+                    "malformed" => policy.malformed_token = treatment,
+                    _ => panic!("{}", USAGE),
+                }
+            }
+
+            match report(&input, &policy) {
+                Ok(report) => print!("{}", report),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(schema_file) => {
+            let schema = Schema::load(schema_file).unwrap_or_else(|e| {
+                eprintln!("{}: {}", schema_file, e);
+                std::process::exit(1);
+            });
+            println!("Valid: {}", count_valid(&input, &schema));
+        }
+    }
 }
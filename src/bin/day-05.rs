@@ -1,13 +1,59 @@
-use aoc_2020::day_05::*;
+use aoc_2020::day_05::{seat_map::SeatMap, *};
+
+const USAGE: &str = "usage: day-05 <input> [--layout <row bits>,<column bits>] [--map]";
 
 fn main() {
-    let input_file = std::env::args().nth(1).unwrap();
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    let mut layout = Layout::STANDARD;
+    if let Some(i) = args.iter().position(|arg| arg == "--layout") {
+        let (rows, cols) = args
+            .get(i + 1)
+            .and_then(|arg| arg.split_once(','))
+            .expect(USAGE);
+        layout = Layout::new(rows.parse().expect(USAGE), cols.parse().expect(USAGE));
+        args.drain(i..i + 2);
+    }
+
+    let input_file = args.first().expect(USAGE);
     let input = std::fs::read_to_string(input_file).unwrap();
 
-    println!("Part 1: {}", part1(&input));
-    if let Some(solution) = part2(&input) {
-        println!("Part 2: {}", solution);
-    } else {
-        println!("No solution for part 2");
+    match args.get(1).map(String::as_str) {
+        None => {
+            match highest_seat_id(&input, layout) {
+                Some(solution) => println!("Part 1: {}", solution),
+                None => println!("No boarding passes"),
+            }
+            if let Some(solution) = missing_seat_id(&input, layout) {
+                println!("Part 2: {}", solution);
+            } else {
+                println!("No solution for part 2");
+            }
+        }
+        Some("--map") => {
+            let map = SeatMap::from_ids(layout, seat_ids(&input, layout));
+            print!("{}", map.render());
+
+            let front = map.missing_front();
+            let back = map.missing_back();
+            println!(
+                "{} taken, {} free: {} missing at the front, {} at the back",
+                map.taken_count(),
+                map.free_ids().count(),
+                front.len(),
+                back.len()
+            );
+            let between: Vec<String> = map.free_between_taken().map(|id| id.to_string()).collect();
+            println!("Free between taken seats: {}", between.join(", "));
+            if let Some(run) = map.longest_free_run() {
+                println!(
+                    "Longest free run: row {}, columns {} to {}",
+                    run.row,
+                    run.cols.start,
+                    run.cols.end - 1
+                );
+            }
+        }
+        Some(_) => panic!("{}", USAGE),
     }
 }
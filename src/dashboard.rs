@@ -0,0 +1,354 @@
+use std::{
+    io,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    answers::KnownAnswers,
+    registry::{self, Answer, SolveError, DAYS},
+};
+
+const TICK: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone)]
+pub enum PartState {
+    NoInput,
+    Running(Instant),
+    Solved { answer: Answer, elapsed: Duration },
+    Unimplemented,
+    Panicked(String),
+    TimedOut,
+}
+
+struct DayState {
+    number: u32,
+    input: Result<String, String>,
+    parts: [PartState; 2],
+    // Bumped on every run so that results from a run that was re-run before it finished are
+    // ignored when they eventually arrive.
+    generation: u64,
+}
+
+struct RunResult {
+    day_index: usize,
+    part: u32,
+    generation: u64,
+    result: Result<Answer, SolveError>,
+    elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Pane {
+    Input,
+    Errors,
+}
+
+pub struct Dashboard {
+    days: Vec<DayState>,
+    known_answers: KnownAnswers,
+    timeout: Duration,
+    table: TableState,
+    pane: Pane,
+    scroll: u16,
+    sender: Sender<RunResult>,
+    receiver: Receiver<RunResult>,
+}
+
+impl Dashboard {
+    pub fn new(known_answers: KnownAnswers, timeout: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        Self {
+            days: DAYS
+                .iter()
+                .map(|day| DayState {
+                    number: day.number,
+                    input: Err("not loaded yet".to_string()),
+                    parts: [PartState::NoInput, PartState::NoInput],
+                    generation: 0,
+                })
+                .collect(),
+            known_answers,
+            timeout,
+            table: TableState::default().with_selected(0),
+            pane: Pane::Input,
+            scroll: 0,
+            sender,
+            receiver,
+        }
+    }
+
+    pub fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        for day_index in 0..self.days.len() {
+            self.start(day_index);
+        }
+
+        loop {
+            self.update();
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(TICK)? {
+                continue;
+            }
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                KeyCode::Char('r') | KeyCode::Enter => self.start(self.selected()),
+                KeyCode::Char('a') => {
+                    for day_index in 0..self.days.len() {
+                        self.start(day_index);
+                    }
+                }
+                KeyCode::Tab => {
+                    self.pane = match self.pane {
+                        Pane::Input => Pane::Errors,
+                        Pane::Errors => Pane::Input,
+                    };
+                    self.scroll = 0;
+                }
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                _ => (),
+            }
+        }
+    }
+
+    /// Takes in the results of finished runs, and gives up on runs past the timeout.
+    pub fn update(&mut self) {
+        self.collect_results();
+        self.check_timeouts();
+    }
+
+    /// The state of one part of the day at `day_index` in `DAYS`.
+    pub fn part(&self, day_index: usize, part: u32) -> &PartState {
+        &self.days[day_index].parts[part as usize - 1]
+    }
+
+    fn selected(&self) -> usize {
+        self.table.selected().unwrap_or(0)
+    }
+
+    fn select(&mut self, offset: isize) {
+        let last = self.days.len() as isize - 1;
+        let selected = (self.selected() as isize + offset).max(0).min(last);
+        self.table.select(Some(selected as usize));
+        self.scroll = 0;
+    }
+
+    /// Re-reads the input so that edits to the file are picked up, then solves both parts on
+    /// background threads. A solver that times out can't be stopped, so its thread is left to
+    /// finish on its own.
+    pub fn start(&mut self, day_index: usize) {
+        let day = &mut self.days[day_index];
+        let path = registry::day(day.number).unwrap().input_path();
+
+        day.generation += 1;
+        day.input = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Couldn't read {}: {}", path.display(), e));
+
+        let input = match &day.input {
+            Ok(input) => input,
+            Err(_) => {
+                day.parts = [PartState::NoInput, PartState::NoInput];
+                return;
+            }
+        };
+
+        for part in 1..=2 {
+            day.parts[part as usize - 1] = PartState::Running(Instant::now());
+
+            let number = day.number;
+            let generation = day.generation;
+            let input = input.clone();
+            let sender = self.sender.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                let result = registry::solve(number, part, &input);
+                let _ = sender.send(RunResult {
+                    day_index,
+                    part,
+                    generation,
+                    result,
+                    elapsed: start.elapsed(),
+                });
+            });
+        }
+    }
+
+    fn collect_results(&mut self) {
+        for run in self.receiver.try_iter() {
+            let day = &mut self.days[run.day_index];
+            let state = &mut day.parts[run.part as usize - 1];
+            // A part that timed out stays that way, even if its solver finishes later.
+            if run.generation != day.generation || !matches!(state, PartState::Running(_)) {
+                continue;
+            }
+
+            *state = match run.result {
+                // It finished, but after the timeout had passed.
+                _ if run.elapsed > self.timeout => PartState::TimedOut,
+                Ok(answer) => PartState::Solved {
+                    answer,
+                    elapsed: run.elapsed,
+                },
+                Err(SolveError::Unimplemented) => PartState::Unimplemented,
+                Err(e) => PartState::Panicked(e.to_string()),
+            };
+        }
+    }
+
+    fn check_timeouts(&mut self) {
+        for day in &mut self.days {
+            for part in &mut day.parts {
+                if let PartState::Running(start) = part {
+                    if start.elapsed() > self.timeout {
+                        *part = PartState::TimedOut;
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [table, pane] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(main);
+
+        frame.render_stateful_widget(self.days_table(), table, &mut self.table);
+        frame.render_widget(self.side_pane(), pane);
+        frame.render_widget(
+            Line::from(
+                " ↑/↓ select   r re-run day   a re-run all   tab input/errors   pgup/pgdn scroll   q quit",
+            )
+            .style(Style::default().add_modifier(Modifier::DIM)),
+            footer,
+        );
+    }
+
+    fn days_table(&self) -> Table<'static> {
+        let header = Row::new(vec![
+            "Day", "Part 1", "Answer", "Time", "", "Part 2", "Answer", "Time", "",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows = self.days.iter().map(|day| {
+            let mut cells = vec![Cell::from(format!("{:>3}", day.number))];
+            for (part, state) in (1..=2).zip(&day.parts) {
+                cells.extend(self.part_cells(day.number, part, state));
+            }
+            Row::new(cells)
+        });
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Length(13),
+                Constraint::Length(16),
+                Constraint::Length(10),
+                Constraint::Length(2),
+                Constraint::Length(13),
+                Constraint::Length(16),
+                Constraint::Length(10),
+                Constraint::Length(2),
+            ],
+        )
+        .header(header)
+        .block(Block::bordered().title(" Advent of Code 2020 "))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    }
+
+    fn part_cells(&self, day: u32, part: u32, state: &PartState) -> Vec<Cell<'static>> {
+        let (status, color) = match state {
+            PartState::NoInput => ("no input", Color::DarkGray),
+            PartState::Running(_) => ("running", Color::Cyan),
+            PartState::Solved { .. } => ("solved", Color::Green),
+            PartState::Unimplemented => ("unimplemented", Color::DarkGray),
+            PartState::Panicked(_) => ("panicked", Color::Red),
+            PartState::TimedOut => ("timed out", Color::Yellow),
+        };
+
+        let (answer, elapsed, check) = match state {
+            PartState::Solved { answer, elapsed } => {
+                let check = match self.known_answers.matches(day, part, *answer) {
+                    Some(true) => Span::styled("✓", Style::default().fg(Color::Green)),
+                    Some(false) => Span::styled("✗", Style::default().fg(Color::Red)),
+                    None => Span::raw("?"),
+                };
+                (answer.to_string(), format!("{:.2?}", elapsed), check)
+            }
+            _ => (String::new(), String::new(), Span::raw("")),
+        };
+
+        vec![
+            Cell::from(Span::styled(status, Style::default().fg(color))),
+            Cell::from(answer),
+            Cell::from(elapsed),
+            Cell::from(check),
+        ]
+    }
+
+    fn side_pane(&self) -> Paragraph<'_> {
+        let day = &self.days[self.selected()];
+
+        let (title, lines) = match (self.pane, &day.input) {
+            (_, Err(e)) => (" Input ", vec![Line::from(e.as_str())]),
+            (Pane::Input, Ok(input)) => (
+                " Input ",
+                input
+                    .lines()
+                    .enumerate()
+                    .map(|(i, line)| {
+                        Line::from(vec![
+                            Span::styled(
+                                format!("{:>4} ", i + 1),
+                                Style::default().add_modifier(Modifier::DIM),
+                            ),
+                            Span::raw(line),
+                        ])
+                    })
+                    .collect(),
+            ),
+            (Pane::Errors, Ok(_)) => {
+                let mut lines: Vec<_> = (1..=2)
+                    .zip(&day.parts)
+                    .filter_map(|(part, state)| match state {
+                        PartState::Panicked(message) => {
+                            Some(Line::from(format!("Part {}: {}", part, message)))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                if lines.is_empty() {
+                    lines.push(Line::from("No errors"));
+                }
+                (" Errors ", lines)
+            }
+        };
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title(format!(" Day {}:{}", day.number, title)))
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0))
+    }
+}
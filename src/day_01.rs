@@ -1,31 +1,390 @@
-use std::collections::HashSet;
+use std::{convert::TryFrom, fmt};
 
-pub fn part1(input: &HashSet<i64>) -> Option<i64> {
-    for num in input.iter() {
-        let find = 2020 - num;
+use itertools::Itertools;
 
-        if input.contains(&find) {
-            return Some(num * find);
+pub const TARGET: i64 = 2020;
+
+/// Finds `k` entries, each from a different position in `entries`, that add up to `target`.
+/// The entries are returned in ascending order.
+pub fn find_k_sum(entries: &[i64], k: usize, target: i64) -> Option<Vec<i64>> {
+    let mut sorted = entries.to_vec();
+    sorted.sort_unstable();
+
+    let mut found = Vec::with_capacity(k);
+    if k_sum(&sorted, k, target.into(), &mut found) {
+        Some(found)
+    } else {
+        None
+    }
+}
+
+// Sums are done in `i128` so that no combination of `i64` entries can overflow.
+fn k_sum(sorted: &[i64], k: usize, target: i128, found: &mut Vec<i64>) -> bool {
+    if k > sorted.len() {
+        return false;
+    }
+
+    // The `k` smallest and `k` largest entries bound every sum we could still make, which prunes
+    // most of the search for large `k`.
+    let min: i128 = sorted[..k].iter().map(|n| i128::from(*n)).sum();
+    let max: i128 = sorted[sorted.len() - k..]
+        .iter()
+        .map(|n| i128::from(*n))
+        .sum();
+    if target < min || target > max {
+        return false;
+    }
+
+    match k {
+        0 => true,
+        1 => {
+            // `target` is between the smallest and largest entries, so it fits in an `i64`.
+            let target = target as i64;
+            let found_target = sorted.binary_search(&target).is_ok();
+            if found_target {
+                found.push(target);
+            }
+            found_target
+        }
+        2 => {
+            let (mut left, mut right) = (0, sorted.len() - 1);
+            while left < right {
+                let sum = i128::from(sorted[left]) + i128::from(sorted[right]);
+                if sum == target {
+                    found.extend([sorted[left], sorted[right]].iter());
+                    return true;
+                } else if sum < target {
+                    left += 1;
+                } else {
+                    right -= 1;
+                }
+            }
+            false
+        }
+        _ => {
+            for (i, first) in sorted.iter().enumerate().take(sorted.len() - k + 1) {
+                // A repeated value can't lead anywhere the first copy didn't.
+                if i > 0 && sorted[i - 1] == *first {
+                    continue;
+                }
+
+                found.push(*first);
+                if k_sum(&sorted[i + 1..], k - 1, target - i128::from(*first), found) {
+                    return true;
+                }
+                found.pop();
+            }
+            false
         }
     }
+}
 
-    None
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Combination {
+    /// Positions in the original entries, in the same order as `values`.
+    pub indices: Vec<usize>,
+    pub values: Vec<i64>,
+}
+
+/// Every combination of `k` entries at distinct positions that adds up to `target`.
+///
+/// Combinations come out in a fixed order. Each one lists its entries sorted by value, then by
+/// position, and the combinations are in lexicographic order of those `(value, position)` lists.
+pub fn k_sums(entries: &[i64], k: usize, target: i64) -> KSums {
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by_key(|i| (entries[*i], *i));
+
+    let sorted: Vec<i64> = order.iter().map(|i| entries[*i]).collect();
+    let prefix_sums = std::iter::once(0)
+        .chain(sorted.iter().scan(0, |sum, n| {
+            *sum += i128::from(*n);
+            Some(*sum)
+        }))
+        .collect();
+
+    KSums {
+        order,
+        sorted,
+        prefix_sums,
+        k,
+        target: target.into(),
+        picks: Vec::with_capacity(k),
+        picks_total: 0,
+        last_picks: 0..0,
+        started: false,
+    }
 }
 
-pub fn part2(input: &HashSet<i64>) -> Option<i64> {
-    for (a_index, a) in input.iter().enumerate() {
-        for b in input.iter().skip(a_index) {
-            if a == b {
-                continue;
+/// Iterator returned by [`k_sums`].
+///
+/// This is a depth-first search over the sorted entries that can be paused after every match.
+/// `picks` holds the first `k - 1` picks, and `last_picks` the run of equal entries that can
+/// complete them.
+#[derive(Debug, Clone)]
+pub struct KSums {
+    order: Vec<usize>,
+    sorted: Vec<i64>,
+    // `prefix_sums[i]` is the sum of `sorted[..i]`.
+    prefix_sums: Vec<i128>,
+    k: usize,
+    target: i128,
+    picks: Vec<usize>,
+    picks_total: i128,
+    last_picks: std::ops::Range<usize>,
+    started: bool,
+}
+
+impl KSums {
+    // Whether `count` more entries, starting at `start`, could possibly add up to `remaining`.
+    fn feasible(&self, start: usize, count: usize, remaining: i128) -> bool {
+        let n = self.sorted.len();
+        if start + count > n {
+            return false;
+        }
+
+        let min = self.prefix_sums[start + count] - self.prefix_sums[start];
+        let max = self.prefix_sums[n] - self.prefix_sums[n - count];
+        min <= remaining && remaining <= max
+    }
+
+    // Moves `picks` on to the next `k - 1` picks that might be completed, trying `start` next.
+    fn advance(&mut self, mut start: usize) -> bool {
+        while self.picks.len() + 1 < self.k {
+            let count = self.k - self.picks.len();
+            let remaining = self.target - self.picks_total;
+
+            if self.feasible(start, count, remaining) {
+                self.picks.push(start);
+                self.picks_total += i128::from(self.sorted[start]);
+                start += 1;
+            } else {
+                // Bigger entries only make the smallest possible sum bigger, so there's nothing
+                // left to try at this depth.
+                match self.picks.pop() {
+                    Some(pick) => {
+                        self.picks_total -= i128::from(self.sorted[pick]);
+                        start = pick + 1;
+                    }
+                    None => return false,
+                }
+            }
+        }
+
+        true
+    }
+
+    fn find_last_picks(&self) -> std::ops::Range<usize> {
+        let start = self.picks.last().map_or(0, |pick| pick + 1);
+        let remaining = self.target - self.picks_total;
+
+        let tail = &self.sorted[start..];
+        let from = tail.partition_point(|n| i128::from(*n) < remaining);
+        let to = tail.partition_point(|n| i128::from(*n) <= remaining);
+        start + from..start + to
+    }
+}
+
+impl Iterator for KSums {
+    type Item = Combination;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.k == 0 {
+            // The empty combination, once, if it's a match.
+            let found = !self.started && self.target == 0;
+            self.started = true;
+            return found.then(|| Combination {
+                indices: vec![],
+                values: vec![],
+            });
+        }
+
+        loop {
+            if let Some(last_pick) = self.last_picks.next() {
+                let picks = self.picks.iter().chain(Some(&last_pick));
+                return Some(Combination {
+                    indices: picks.clone().map(|pick| self.order[*pick]).collect(),
+                    values: picks.map(|pick| self.sorted[*pick]).collect(),
+                });
+            }
+
+            let start = if !self.started {
+                self.started = true;
+                0
+            } else {
+                match self.picks.pop() {
+                    Some(pick) => {
+                        self.picks_total -= i128::from(self.sorted[pick]);
+                        pick + 1
+                    }
+                    None => return None,
+                }
+            };
+
+            if !self.advance(start) {
+                return None;
             }
+            self.last_picks = self.find_last_picks();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Solutions {
+    None,
+    Unique(Combination),
+    /// More than one combination matches; holds how many.
+    Ambiguous(usize),
+}
+
+pub fn summarize_k_sums(entries: &[i64], k: usize, target: i64) -> Solutions {
+    let mut combinations = k_sums(entries, k, target);
+
+    match (combinations.next(), combinations.next()) {
+        (None, _) => Solutions::None,
+        (Some(combination), None) => Solutions::Unique(combination),
+        (Some(_), Some(_)) => Solutions::Ambiguous(2 + combinations.count()),
+    }
+}
 
-            let c = 2020 - a - b;
+#[derive(Debug, Clone, Copy)]
+pub struct SubsetSumConfig {
+    /// The most entries a subset may have.
+    pub max_size: usize,
+    /// The most memory the dynamic-programming table may use.
+    pub max_table_bytes: usize,
+}
+
+impl Default for SubsetSumConfig {
+    fn default() -> Self {
+        Self {
+            max_size: usize::MAX,
+            max_table_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SubsetSumError {
+    /// The table for this target and size limit would need `required` bytes.
+    TableTooLarge { required: usize, limit: usize },
+}
+
+/// Finds the smallest subset of entries, of at most `config.max_size` entries, that adds up to
+/// `target`. The subset's entries are returned in input order.
+///
+/// Non-negative entries are solved by dynamic programming over subset size and sum, in
+/// `O(entries * max_size * target)` time. If any entry is negative, sums can't be used as table
+/// indices, so every size is searched in turn with [`k_sums`] instead, which can be exponential.
+pub fn find_subset_sum(
+    entries: &[i64],
+    target: i64,
+    config: SubsetSumConfig,
+) -> Result<Option<Combination>, SubsetSumError> {
+    let max_size = config.max_size.min(entries.len());
+
+    if entries.iter().any(|n| *n < 0) {
+        let found = (1..=max_size).find_map(|k| k_sums(entries, k, target).next());
+        return Ok(found.map(|combination| {
+            let (indices, values) = combination
+                .indices
+                .into_iter()
+                .zip(combination.values)
+                .sorted()
+                .unzip();
+            Combination { indices, values }
+        }));
+    }
 
-            if c > 0 && input.contains(&c) {
-                return Some(a * b * c);
+    let target = match usize::try_from(target) {
+        Ok(target) => target,
+        Err(_) => return Ok(None),
+    };
+
+    let too_large = |required| SubsetSumError::TableTooLarge {
+        required,
+        limit: config.max_table_bytes,
+    };
+    let width = target.checked_add(1).ok_or_else(|| too_large(usize::MAX))?;
+    let cells = (max_size + 1)
+        .checked_mul(width)
+        .ok_or_else(|| too_large(usize::MAX))?;
+    let required = cells
+        .checked_mul(std::mem::size_of::<u32>())
+        .ok_or_else(|| too_large(usize::MAX))?;
+    if required > config.max_table_bytes {
+        return Err(too_large(required));
+    }
+
+    // `via[size * width + sum]` is 1 + the index of the entry that first made a subset of `size`
+    // entries adding up to `sum`, or 0 if no such subset has been found yet. The empty subset
+    // needs no entry at all, so it gets a marker of its own.
+    const EMPTY: u32 = u32::MAX;
+    let mut via = vec![0; cells];
+    via[0] = EMPTY;
+
+    for (i, value) in entries.iter().enumerate() {
+        let marker = u32::try_from(i + 1).expect("too many entries");
+        let value = *value as usize;
+        if value > target {
+            continue;
+        }
+
+        // Going from large subsets to small ones means row `size - 1` doesn't yet include this
+        // entry, so no subset uses it twice.
+        for size in (1..=max_size).rev() {
+            for sum in value..width {
+                let cell = size * width + sum;
+                if via[cell] == 0 && via[cell - width - value] != 0 {
+                    via[cell] = marker;
+                }
             }
         }
     }
 
-    None
+    let size = match (1..=max_size).find(|size| via[size * width + target] != 0) {
+        Some(size) => size,
+        None => return Ok(None),
+    };
+
+    // Each step back goes to a subset that was found before the entry that extended it, so the
+    // indices strictly decrease and no entry is used twice.
+    let mut indices = Vec::with_capacity(size);
+    let mut sum = target;
+    for size in (1..=size).rev() {
+        let i = via[size * width + sum] as usize - 1;
+        indices.push(i);
+        sum -= entries[i] as usize;
+    }
+    indices.reverse();
+
+    Ok(Some(Combination {
+        values: indices.iter().map(|i| entries[*i]).collect(),
+        indices,
+    }))
+}
+
+fn product_of_k_sum(input: &[i64], k: usize) -> Option<i64> {
+    find_k_sum(input, k, TARGET).map(|found| found.iter().product())
+}
+
+pub fn part1(input: &[i64]) -> Option<i64> {
+    product_of_k_sum(input, 2)
+}
+
+pub fn part2(input: &[i64]) -> Option<i64> {
+    product_of_k_sum(input, 3)
 }
+
+impl fmt::Display for SubsetSumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TableTooLarge { required, limit } => write!(
+                f,
+                "subset-sum table would need {} bytes, but the limit is {}",
+                required, limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SubsetSumError {}
@@ -1,68 +1,501 @@
-use regex::{Captures, Regex};
+use std::fmt;
 
-lazy_static::lazy_static! {
-    static ref REGEX: Regex = Regex::new(
-        r"^(?P<lower_bound>\d+)-(?P<upper_bound>\d+) (?P<required>[a-z]): (?P<password>[a-z]+)$",
-    )
-    .unwrap();
+use {regex::Regex, serde::Serialize};
 
-}
+pub mod policy_file;
 
-#[derive(Debug)]
-struct PasswordEntry {
+/// One line of input, `<lower>-<upper> <letter>: <password>`, borrowing its password from the
+/// line.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PasswordEntry<'a> {
     pub lower_bound: usize,
     pub upper_bound: usize,
     pub required: char,
-    pub password: String,
+    pub password: &'a str,
 }
 
-impl PasswordEntry {
-    pub fn from_captures(caps: Captures) -> Self {
-        let lower_bound = caps["lower_bound"].parse().unwrap();
-        let upper_bound = caps["upper_bound"].parse().unwrap();
-        let required = caps["required"].chars().next().unwrap();
-        let password = caps["password"].to_string();
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseError {
+    /// The line doesn't have the `<lower>-<upper> <letter>: <password>` shape.
+    Malformed,
+    InvalidBound(String),
+    /// The letter isn't exactly one character.
+    InvalidLetter(String),
+    EmptyPassword,
+}
 
-        Self {
-            lower_bound,
-            upper_bound,
+/// Why a 1-based position can't be looked up in a password.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PositionError {
+    /// Positions start at 1.
+    Zero,
+    /// The password only has `len` characters.
+    PastEnd { position: usize, len: usize },
+}
+
+impl<'a> PasswordEntry<'a> {
+    pub fn parse(line: &'a str) -> Result<Self, ParseError> {
+        let (bounds, rest) = line.split_once(' ').ok_or(ParseError::Malformed)?;
+        let (required, password) = rest.split_once(": ").ok_or(ParseError::Malformed)?;
+        let (lower_bound, upper_bound) = bounds.split_once('-').ok_or(ParseError::Malformed)?;
+
+        let bound = |bound: &str| {
+            bound
+                .parse()
+                .map_err(|_| ParseError::InvalidBound(bound.to_string()))
+        };
+
+        let mut chars = required.chars();
+        let required = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(ParseError::InvalidLetter(required.to_string())),
+        };
+
+        if password.is_empty() {
+            return Err(ParseError::EmptyPassword);
+        }
+
+        Ok(Self {
+            lower_bound: bound(lower_bound)?,
+            upper_bound: bound(upper_bound)?,
             required,
             password,
+        })
+    }
+
+    /// The character at 1-based `position`. Positions count characters, not bytes, so a
+    /// multi-byte character takes up a single position.
+    pub fn char_at(&self, position: usize) -> Result<char, PositionError> {
+        if position == 0 {
+            return Err(PositionError::Zero);
+        }
+
+        self.password
+            .chars()
+            .nth(position - 1)
+            .ok_or_else(|| PositionError::PastEnd {
+                position,
+                len: self.password.chars().count(),
+            })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Violation {
+    pub policy: String,
+    pub reason: String,
+}
+
+impl Violation {
+    fn new(policy: &str, reason: String) -> Self {
+        Self {
+            policy: policy.to_string(),
+            reason,
         }
     }
+}
+
+pub trait PasswordPolicy {
+    /// Every way `entry` breaks this policy, or nothing if it's valid.
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation>;
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        self.violations(entry).is_empty()
+    }
+}
 
-    pub fn is_valid_part1(&self) -> bool {
-        let count = self
+/// The part 1 policy: the entry's letter appears between `lower_bound` and `upper_bound` times.
+#[derive(Debug, Clone, Copy)]
+pub struct CountInRange;
+
+impl PasswordPolicy for CountInRange {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        let count = entry
             .password
             .chars()
-            .filter(|c| *c == self.required)
+            .filter(|c| *c == entry.required)
             .count();
 
-        self.lower_bound <= count && count <= self.upper_bound
+        if entry.lower_bound <= count && count <= entry.upper_bound {
+            vec![]
+        } else {
+            vec![Violation::new(
+                "count",
+                format!(
+                    "{:?} appears {} times, need {}–{}",
+                    entry.required, count, entry.lower_bound, entry.upper_bound
+                ),
+            )]
+        }
     }
+}
+
+/// The part 2 policy: exactly one of the positions `lower_bound` and `upper_bound` holds the
+/// entry's letter. Positions are 1-based and count characters, as in [`PasswordEntry::char_at`].
+/// A position outside the password is a violation.
+#[derive(Debug, Clone, Copy)]
+pub struct PositionXor;
 
-    pub fn is_valid_part2(&self) -> bool {
-        let char1 = self.password.chars().nth(self.lower_bound - 1).unwrap();
-        let char2 = self.password.chars().nth(self.upper_bound - 1).unwrap();
+impl PasswordPolicy for PositionXor {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        let (char1, char2) = match (
+            entry.char_at(entry.lower_bound),
+            entry.char_at(entry.upper_bound),
+        ) {
+            (Ok(char1), Ok(char2)) => (char1, char2),
+            (Err(e), _) | (_, Err(e)) => return vec![Violation::new("positions", e.to_string())],
+        };
 
-        (char1 == self.required) ^ (char2 == self.required)
+        let reason = match (char1 == entry.required, char2 == entry.required) {
+            (true, true) => "both contain",
+            (false, false) => "both lack",
+            _ => return vec![],
+        };
+
+        vec![Violation::new(
+            "positions",
+            format!(
+                "positions {} and {} {} {:?}",
+                entry.lower_bound, entry.upper_bound, reason, entry.required
+            ),
+        )]
     }
 }
 
-pub fn part1(input: &[String]) -> usize {
-    input
+/// The password is between `min` and `max` characters (not bytes) long, inclusive.
+#[derive(Debug, Clone, Copy)]
+pub struct Length {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl PasswordPolicy for Length {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        let len = entry.password.chars().count();
+
+        if self.min <= len && len <= self.max {
+            vec![]
+        } else {
+            vec![Violation::new(
+                "length",
+                format!(
+                    "password is {} characters long, need {}–{}",
+                    len, self.min, self.max
+                ),
+            )]
+        }
+    }
+}
+
+/// A fixed letter appears between `min` and `max` times, inclusive.
+#[derive(Debug, Clone, Copy)]
+pub struct LetterCount {
+    pub letter: char,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl PasswordPolicy for LetterCount {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        let count = entry.password.chars().filter(|c| *c == self.letter).count();
+
+        if self.min <= count && count <= self.max {
+            vec![]
+        } else {
+            vec![Violation::new(
+                "count",
+                format!(
+                    "{:?} appears {} times, need {}–{}",
+                    self.letter, count, self.min, self.max
+                ),
+            )]
+        }
+    }
+}
+
+/// What a single character should be.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CharTest {
+    /// The entry's own letter.
+    EntryLetter,
+    Char(char),
+    Class(CharClass),
+}
+
+impl CharTest {
+    pub fn matches(self, entry: &PasswordEntry, c: char) -> bool {
+        match self {
+            Self::EntryLetter => c == entry.required,
+            Self::Char(expected) => c == expected,
+            Self::Class(class) => class.contains(c),
+        }
+    }
+}
+
+/// The character at 1-based `position` passes `expected`. Positions count characters, as in
+/// [`PasswordEntry::char_at`].
+#[derive(Debug, Clone, Copy)]
+pub struct CharAt {
+    pub position: usize,
+    pub expected: CharTest,
+}
+
+impl PasswordPolicy for CharAt {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        let reason = match entry.char_at(self.position) {
+            Ok(c) if self.expected.matches(entry, c) => return vec![],
+            Ok(c) => {
+                let expected = match self.expected {
+                    CharTest::EntryLetter => format!("{:?}", entry.required),
+                    CharTest::Char(letter) => format!("{:?}", letter),
+                    CharTest::Class(class) => format!("a {} character", class),
+                };
+                format!("position {} is {:?}, need {}", self.position, c, expected)
+            }
+            Err(e) => e.to_string(),
+        };
+
+        vec![Violation::new("position", reason)]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CharClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    /// ASCII punctuation.
+    Symbol,
+}
+
+impl CharClass {
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            Self::Lowercase => c.is_lowercase(),
+            Self::Uppercase => c.is_uppercase(),
+            Self::Digit => c.is_ascii_digit(),
+            Self::Symbol => c.is_ascii_punctuation(),
+        }
+    }
+}
+
+/// The password has at least one character from each class.
+#[derive(Debug, Clone)]
+pub struct RequiredClasses(pub Vec<CharClass>);
+
+impl PasswordPolicy for RequiredClasses {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        self.0
+            .iter()
+            .filter(|class| !entry.password.chars().any(|c| class.contains(c)))
+            .map(|class| Violation::new("classes", format!("no {} character", class)))
+            .collect()
+    }
+}
+
+/// The password contains none of the substrings.
+#[derive(Debug, Clone)]
+pub struct ForbiddenSubstrings(pub Vec<String>);
+
+impl PasswordPolicy for ForbiddenSubstrings {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        self.0
+            .iter()
+            .filter(|substring| entry.password.contains(substring.as_str()))
+            .map(|substring| Violation::new("forbidden", format!("contains {:?}", substring)))
+            .collect()
+    }
+}
+
+/// The password matches the regex somewhere. Anchor it to match the whole password.
+#[derive(Debug, Clone)]
+pub struct RegexMatch(pub Regex);
+
+impl PasswordPolicy for RegexMatch {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        if self.0.is_match(entry.password) {
+            vec![]
+        } else {
+            vec![Violation::new(
+                "regex",
+                format!("does not match /{}/", self.0.as_str()),
+            )]
+        }
+    }
+}
+
+/// Valid only if every policy is.
+pub struct All(pub Vec<Box<dyn PasswordPolicy>>);
+
+impl PasswordPolicy for All {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        self.0
+            .iter()
+            .flat_map(|policy| policy.violations(entry))
+            .collect()
+    }
+}
+
+/// Valid if any policy is. An entry that fails them all gets one violation listing every reason.
+pub struct Any(pub Vec<Box<dyn PasswordPolicy>>);
+
+impl PasswordPolicy for Any {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        let mut reasons = Vec::new();
+
+        for policy in &self.0 {
+            let violations = policy.violations(entry);
+            if violations.is_empty() {
+                return vec![];
+            }
+            reasons.extend(
+                violations
+                    .into_iter()
+                    .map(|violation| format!("{}: {}", violation.policy, violation.reason)),
+            );
+        }
+
+        vec![Violation::new(
+            "any",
+            format!("none of the alternatives hold ({})", reasons.join("; ")),
+        )]
+    }
+}
+
+/// Valid only if `policy` isn't. `description` says what `policy` checks, since there's no
+/// violation of it to explain the failure.
+pub struct Not {
+    pub policy: Box<dyn PasswordPolicy>,
+    pub description: String,
+}
+
+impl PasswordPolicy for Not {
+    fn violations(&self, entry: &PasswordEntry) -> Vec<Violation> {
+        if self.policy.is_valid(entry) {
+            vec![Violation::new(
+                "not",
+                format!("{} holds, but shouldn't", self.description),
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Failure {
+    /// 1-based.
+    pub line: usize,
+    pub entry: String,
+    pub violations: Vec<Violation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub valid: usize,
+    pub invalid: usize,
+    pub failures: Vec<Failure>,
+}
+
+/// Checks every entry against `policy`, keeping the details of each one that fails.
+pub fn report(input: &[String], policy: &dyn PasswordPolicy) -> Report {
+    let failures: Vec<Failure> = input
         .iter()
-        .map(|line| REGEX.captures(line).unwrap())
-        .map(PasswordEntry::from_captures)
-        .filter(|password| password.is_valid_part1())
-        .count()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let violations = match PasswordEntry::parse(line) {
+                Ok(entry) => policy.violations(&entry),
+                Err(e) => vec![Violation::new("parse", e.to_string())],
+            };
+            if violations.is_empty() {
+                None
+            } else {
+                Some(Failure {
+                    line: i + 1,
+                    entry: line.clone(),
+                    violations,
+                })
+            }
+        })
+        .collect();
+
+    Report {
+        valid: input.len() - failures.len(),
+        invalid: failures.len(),
+        failures,
+    }
 }
 
-pub fn part2(input: &[String]) -> usize {
+pub fn count_valid(input: &[String], policy: &dyn PasswordPolicy) -> usize {
     input
         .iter()
-        .map(|line| REGEX.captures(line).unwrap())
-        .map(PasswordEntry::from_captures)
-        .filter(|password| password.is_valid_part2())
+        .enumerate()
+        .map(|(i, line)| {
+            PasswordEntry::parse(line).unwrap_or_else(|e| panic!("line {}: {}", i + 1, e))
+        })
+        .filter(|entry| policy.is_valid(entry))
         .count()
 }
+
+pub fn part1(input: &[String]) -> usize {
+    count_valid(input, &CountInRange)
+}
+
+pub fn part2(input: &[String]) -> usize {
+    count_valid(input, &PositionXor)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "expected `<lower>-<upper> <letter>: <password>`"),
+            Self::InvalidBound(bound) => write!(f, "invalid bound {:?}", bound),
+            Self::InvalidLetter(letter) => {
+                write!(f, "expected a single letter, found {:?}", letter)
+            }
+            Self::EmptyPassword => write!(f, "password is empty"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Zero => write!(f, "position 0 is out of range, positions start at 1"),
+            Self::PastEnd { position, len } => write!(
+                f,
+                "position {} is past the end of a {}-character password",
+                position, len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Lowercase => "lowercase",
+            Self::Uppercase => "uppercase",
+            Self::Digit => "digit",
+            Self::Symbol => "symbol",
+        };
+        f.pad(name)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for failure in &self.failures {
+            writeln!(f, "line {}: {}", failure.line, failure.entry)?;
+            for violation in &failure.violations {
+                writeln!(f, "    {}: {}", violation.policy, violation.reason)?;
+            }
+        }
+
+        writeln!(f, "{} valid, {} invalid", self.valid, self.invalid)
+    }
+}
@@ -0,0 +1,503 @@
+//! A small language for writing password policies outside of Rust.
+//!
+//! A policy file is a single expression. Constraints are combined with `and`, `or` and `not`,
+//! which bind in that order from loosest to tightest, and can be grouped with parentheses. `#`
+//! starts a comment that runs to the end of the line.
+//!
+//! | Constraint             | Holds when                                                        |
+//! |------------------------|-------------------------------------------------------------------|
+//! | `count`                | the entry's letter appears within the entry's bounds (part 1)     |
+//! | `count 'a' 1-3`        | `a` appears between 1 and 3 times                                 |
+//! | `positions`            | exactly one of the entry's positions holds its letter (part 2)    |
+//! | `position 3 is <test>` | the third character is `letter` (the entry's), `'a'`, or a class |
+//! | `length 8-64`          | the password is 8 to 64 characters long                           |
+//! | `has <class>`          | some character is in the class                                    |
+//! | `forbid "abc"`         | the password doesn't contain `abc`                                |
+//! | `matches /^[a-z]+$/`   | the regex matches somewhere in the password                       |
+//!
+//! The classes are `lowercase`, `uppercase`, `digit` and `symbol`. A string can't contain `"`, and
+//! a regex writes `/` as `\/`. Both have to fit on one line. For example:
+//!
+//! ```text
+//! # Part 1 or part 2, but never a password that starts with a digit.
+//! (count or positions) and not position 1 is digit
+//! ```
+
+use std::fmt;
+
+use regex::Regex;
+
+use super::{
+    All, Any, CharAt, CharClass, CharTest, CountInRange, ForbiddenSubstrings, Length, LetterCount,
+    Not, PasswordPolicy, PositionXor, RegexMatch, RequiredClasses,
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PolicyError {
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, in characters.
+    pub column: usize,
+    pub message: String,
+}
+
+/// Parses a policy file into a policy that can be applied to entries.
+pub fn parse(source: &str) -> Result<Box<dyn PasswordPolicy>, PolicyError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        next: 0,
+        end: end_of(source),
+    };
+
+    let expr = parser.or()?;
+    if let Some(token) = parser.peek() {
+        return Err(token.error(format!(
+            "expected `and`, `or` or the end of the file, found {}",
+            token.token
+        )));
+    }
+
+    Ok(expr.compile())
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Token<'a> {
+    Word(&'a str),
+    Number(usize),
+    Char(char),
+    /// The text between the quotes.
+    Str(&'a str),
+    /// The text between the slashes, still with `\/` for `/`.
+    Regex(&'a str),
+    Dash,
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Spanned<'a> {
+    token: Token<'a>,
+    line: usize,
+    column: usize,
+}
+
+impl Spanned<'_> {
+    fn error(&self, message: String) -> PolicyError {
+        PolicyError {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+}
+
+// Where the end of the file is, for errors about something missing there.
+fn end_of(source: &str) -> (usize, usize) {
+    let line = source.split('\n').count();
+    let column = source.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+fn tokenize(source: &str) -> Result<Vec<Spanned<'_>>, PolicyError> {
+    let mut tokens = Vec::new();
+
+    for (line_index, source_line) in source.split('\n').enumerate() {
+        let mut chars = source_line.char_indices().enumerate().peekable();
+
+        while let Some((column_index, (start, c))) = chars.next() {
+            let (line, column) = (line_index + 1, column_index + 1);
+            let error = |message: String| PolicyError {
+                line,
+                column,
+                message,
+            };
+
+            let token = match c {
+                '#' => break,
+                c if c.is_whitespace() => continue,
+                '-' => Token::Dash,
+                '(' => Token::Open,
+                ')' => Token::Close,
+                '\'' => match (chars.next(), chars.next()) {
+                    (Some((_, (_, c))), Some((_, (_, '\'')))) => Token::Char(c),
+                    _ => return Err(error("expected a character like 'a'".to_string())),
+                },
+                '"' | '/' => {
+                    let mut escaped = false;
+                    let mut end = None;
+                    for (_, (i, next)) in chars.by_ref() {
+                        if next == c && !escaped {
+                            end = Some(i);
+                            break;
+                        }
+                        escaped = c == '/' && next == '\\' && !escaped;
+                    }
+
+                    let kind = if c == '"' { "string" } else { "regex" };
+                    let end = end.ok_or_else(|| error(format!("unterminated {}", kind)))?;
+                    let text = &source_line[start + 1..end];
+                    if c == '"' {
+                        Token::Str(text)
+                    } else {
+                        Token::Regex(text)
+                    }
+                }
+                c if c.is_ascii_digit() || c.is_alphabetic() || c == '_' => {
+                    let mut end = start + c.len_utf8();
+                    while let Some((_, (i, c))) = chars.peek() {
+                        if !(c.is_ascii_digit() || c.is_alphabetic() || *c == '_') {
+                            break;
+                        }
+                        end = i + c.len_utf8();
+                        chars.next();
+                    }
+
+                    let text = &source_line[start..end];
+                    if c.is_ascii_digit() {
+                        let number = text
+                            .parse()
+                            .map_err(|_| error(format!("invalid number {:?}", text)))?;
+                        Token::Number(number)
+                    } else {
+                        Token::Word(text)
+                    }
+                }
+                c => return Err(error(format!("unexpected {:?}", c))),
+            };
+
+            tokens.push(Spanned {
+                token,
+                line,
+                column,
+            });
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Expr {
+    Count,
+    LetterCount {
+        letter: char,
+        min: usize,
+        max: usize,
+    },
+    Positions,
+    Position {
+        position: usize,
+        expected: CharTest,
+    },
+    Length {
+        min: usize,
+        max: usize,
+    },
+    Has(CharClass),
+    Forbid(String),
+    /// Checked to be a valid regex when it's parsed.
+    Matches(String),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+impl Expr {
+    fn compile(&self) -> Box<dyn PasswordPolicy> {
+        match self {
+            Self::Count => Box::new(CountInRange),
+            Self::LetterCount { letter, min, max } => Box::new(LetterCount {
+                letter: *letter,
+                min: *min,
+                max: *max,
+            }),
+            Self::Positions => Box::new(PositionXor),
+            Self::Position { position, expected } => Box::new(CharAt {
+                position: *position,
+                expected: *expected,
+            }),
+            Self::Length { min, max } => Box::new(Length {
+                min: *min,
+                max: *max,
+            }),
+            Self::Has(class) => Box::new(RequiredClasses(vec![*class])),
+            Self::Forbid(substring) => Box::new(ForbiddenSubstrings(vec![substring.clone()])),
+            Self::Matches(pattern) => Box::new(RegexMatch(Regex::new(pattern).unwrap())),
+            Self::Not(expr) => Box::new(Not {
+                policy: expr.compile(),
+                description: format!("`{}`", expr),
+            }),
+            Self::And(exprs) => Box::new(All(exprs.iter().map(Self::compile).collect())),
+            Self::Or(exprs) => Box::new(Any(exprs.iter().map(Self::compile).collect())),
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<Spanned<'a>>,
+    next: usize,
+    end: (usize, usize),
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Spanned<'a>> {
+        self.tokens.get(self.next).copied()
+    }
+
+    fn peek_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Spanned { token: Token::Word(w), .. }) if w == word)
+    }
+
+    /// The next token, or an error saying that `expected` was wanted instead of the end of the
+    /// file.
+    fn take(&mut self, expected: &str) -> Result<Spanned<'a>, PolicyError> {
+        match self.peek() {
+            Some(token) => {
+                self.next += 1;
+                Ok(token)
+            }
+            None => Err(PolicyError {
+                line: self.end.0,
+                column: self.end.1,
+                message: format!("expected {}, found the end of the file", expected),
+            }),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, PolicyError> {
+        let mut exprs = vec![self.and()?];
+        while self.peek_word("or") {
+            self.next += 1;
+            exprs.push(self.and()?);
+        }
+
+        Ok(if exprs.len() == 1 {
+            exprs.pop().unwrap()
+        } else {
+            Expr::Or(exprs)
+        })
+    }
+
+    fn and(&mut self) -> Result<Expr, PolicyError> {
+        let mut exprs = vec![self.unary()?];
+        while self.peek_word("and") {
+            self.next += 1;
+            exprs.push(self.unary()?);
+        }
+
+        Ok(if exprs.len() == 1 {
+            exprs.pop().unwrap()
+        } else {
+            Expr::And(exprs)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Expr, PolicyError> {
+        if self.peek_word("not") {
+            self.next += 1;
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+
+        let token = self.take("a constraint")?;
+        match token.token {
+            Token::Open => {
+                let expr = self.or()?;
+                let close = self.take("`)`")?;
+                if close.token != Token::Close {
+                    return Err(close.error(format!("expected `)`, found {}", close.token)));
+                }
+                Ok(expr)
+            }
+            Token::Word("count") => match self.peek() {
+                Some(Spanned {
+                    token: Token::Char(letter),
+                    ..
+                }) => {
+                    self.next += 1;
+                    let (min, max) = self.range()?;
+                    Ok(Expr::LetterCount { letter, min, max })
+                }
+                _ => Ok(Expr::Count),
+            },
+            Token::Word("positions") => Ok(Expr::Positions),
+            Token::Word("position") => {
+                let number = self.take("a position")?;
+                let position = match number.token {
+                    Token::Number(0) => {
+                        return Err(number.error("positions start at 1".to_string()))
+                    }
+                    Token::Number(position) => position,
+                    other => {
+                        return Err(number.error(format!("expected a position, found {}", other)))
+                    }
+                };
+
+                let is = self.take("`is`")?;
+                if is.token != Token::Word("is") {
+                    return Err(is.error(format!("expected `is`, found {}", is.token)));
+                }
+
+                let test = self.take("`letter`, a character or a class")?;
+                let expected = match test.token {
+                    Token::Word("letter") => CharTest::EntryLetter,
+                    Token::Char(c) => CharTest::Char(c),
+                    _ => CharTest::Class(class(test).map_err(|_| {
+                        test.error(format!(
+                            "expected `letter`, a character or a class, found {}",
+                            test.token
+                        ))
+                    })?),
+                };
+
+                Ok(Expr::Position { position, expected })
+            }
+            Token::Word("length") => {
+                let (min, max) = self.range()?;
+                Ok(Expr::Length { min, max })
+            }
+            Token::Word("has") => {
+                let token = self.take("a class")?;
+                Ok(Expr::Has(class(token)?))
+            }
+            Token::Word("forbid") => {
+                let token = self.take("a string")?;
+                match token.token {
+                    Token::Str("") => Err(token.error("every password contains \"\"".to_string())),
+                    Token::Str(substring) => Ok(Expr::Forbid(substring.to_string())),
+                    other => Err(token.error(format!("expected a string, found {}", other))),
+                }
+            }
+            Token::Word("matches") => {
+                let token = self.take("a regex")?;
+                match token.token {
+                    Token::Regex(pattern) => {
+                        let pattern = pattern.replace("\\/", "/");
+                        Regex::new(&pattern).map_err(|e| token.error(e.to_string()))?;
+                        Ok(Expr::Matches(pattern))
+                    }
+                    other => Err(token.error(format!("expected a regex, found {}", other))),
+                }
+            }
+            other => Err(token.error(format!("expected a constraint, found {}", other))),
+        }
+    }
+
+    /// `<min>-<max>`, inclusive.
+    fn range(&mut self) -> Result<(usize, usize), PolicyError> {
+        let number = |parser: &mut Self| {
+            let token = parser.take("a number")?;
+            match token.token {
+                Token::Number(n) => Ok((token, n)),
+                other => Err(token.error(format!("expected a number, found {}", other))),
+            }
+        };
+
+        let (start, min) = number(self)?;
+        let dash = self.take("`-`")?;
+        if dash.token != Token::Dash {
+            return Err(dash.error(format!("expected `-`, found {}", dash.token)));
+        }
+        let (_, max) = number(self)?;
+
+        if min > max {
+            return Err(start.error(format!("the range {}-{} is empty", min, max)));
+        }
+        Ok((min, max))
+    }
+}
+
+fn class(token: Spanned<'_>) -> Result<CharClass, PolicyError> {
+    match token.token {
+        Token::Word("lowercase") => Ok(CharClass::Lowercase),
+        Token::Word("uppercase") => Ok(CharClass::Uppercase),
+        Token::Word("digit") => Ok(CharClass::Digit),
+        Token::Word("symbol") => Ok(CharClass::Symbol),
+        other => Err(token.error(format!(
+            "expected `lowercase`, `uppercase`, `digit` or `symbol`, found {}",
+            other
+        ))),
+    }
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "`{}`", word),
+            Self::Number(n) => write!(f, "`{}`", n),
+            Self::Char(c) => write!(f, "`'{}'`", c),
+            Self::Str(s) => write!(f, "`\"{}\"`", s),
+            Self::Regex(pattern) => write!(f, "`/{}/`", pattern),
+            Self::Dash => write!(f, "`-`"),
+            Self::Open => write!(f, "`(`"),
+            Self::Close => write!(f, "`)`"),
+        }
+    }
+}
+
+// Writes the expression back out in the policy language, for describing what `not` rejected.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Sub-expressions that bind more loosely than their parent need parentheses.
+        let grouped = |expr: &Expr, f: &mut fmt::Formatter<'_>, loose: bool| {
+            if loose {
+                write!(f, "({})", expr)
+            } else {
+                write!(f, "{}", expr)
+            }
+        };
+
+        match self {
+            Self::Count => write!(f, "count"),
+            Self::LetterCount { letter, min, max } => {
+                write!(f, "count '{}' {}-{}", letter, min, max)
+            }
+            Self::Positions => write!(f, "positions"),
+            Self::Position { position, expected } => {
+                write!(f, "position {} is ", position)?;
+                match expected {
+                    CharTest::EntryLetter => write!(f, "letter"),
+                    CharTest::Char(c) => write!(f, "'{}'", c),
+                    CharTest::Class(class) => write!(f, "{}", class),
+                }
+            }
+            Self::Length { min, max } => write!(f, "length {}-{}", min, max),
+            Self::Has(class) => write!(f, "has {}", class),
+            Self::Forbid(substring) => write!(f, "forbid \"{}\"", substring),
+            Self::Matches(pattern) => write!(f, "matches /{}/", pattern.replace('/', "\\/")),
+            Self::Not(expr) => {
+                write!(f, "not ")?;
+                grouped(expr, f, matches!(**expr, Self::And(_) | Self::Or(_)))
+            }
+            Self::And(exprs) => {
+                for (i, expr) in exprs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " and ")?;
+                    }
+                    grouped(expr, f, matches!(expr, Self::Or(_)))?;
+                }
+                Ok(())
+            }
+            Self::Or(exprs) => {
+                for (i, expr) in exprs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " or ")?;
+                    }
+                    grouped(expr, f, false)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for PolicyError {}
@@ -1,5 +1,9 @@
-#[derive(Clone, Copy, Eq, PartialEq)]
-enum Tile {
+use std::cmp::Reverse;
+
+pub mod render;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Tile {
     Open,
     Tree,
 }
@@ -14,57 +18,390 @@ impl Tile {
     }
 }
 
-struct Map {
+/// How far right (`dx`) and down (`dy`) the toboggan moves each step.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Slope {
+    pub dx: usize,
+    pub dy: usize,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SlopeCount {
+    pub slope: Slope,
+    pub trees: u64,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Objective {
+    FewestTrees,
+    MostTrees,
+}
+
+/// What lies past the edges of the map.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum Edges {
+    /// The map repeats to the right, and a run ends at the bottom.
+    #[default]
+    Wrap,
+    /// Nothing but the map itself. A run ends when it leaves the map, at the bottom or the side.
+    Bounded,
+    /// The map repeats to the right, every other copy flipped left to right. A run ends at the
+    /// bottom.
+    Mirrored,
+    /// The map repeats to the right and below, so a run never ends. It's cut short once it comes
+    /// back to a square it has already visited, since it would only repeat itself from there.
+    Torus,
+}
+
+/// Why a run stopped.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RunEnd {
+    Bottom,
+    /// Left the map past its right edge. Only happens with [`Edges::Bounded`].
+    Side,
+    /// Came back to a square it had already visited. Only happens with [`Edges::Torus`].
+    Cycle,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Run {
+    /// Squares visited, counting the starting square.
+    pub squares: usize,
+    pub trees: u64,
+    pub end: RunEnd,
+}
+
+/// The map as one bitset per row, with a set bit for each tree.
+#[derive(Debug, Clone)]
+pub struct Map {
     width: usize,
     height: usize,
-    tiles: Vec<Tile>,
+    words_per_row: usize,
+    bits: Vec<u64>,
+    edges: Edges,
 }
 
 impl Map {
-    pub fn from_str(input: &str) -> Self {
+    /// The first row sets the width. Longer rows are cut to it, and shorter ones are padded with
+    /// open squares.
+    pub fn parse(input: &str) -> Self {
+        let width = input.find('\n').unwrap();
+        let words_per_row = width.div_ceil(64);
+
+        let mut bits = Vec::new();
+        for line in input.lines() {
+            let mut row = vec![0; words_per_row];
+            for (x, tile) in line
+                .chars()
+                .filter_map(Tile::from_char)
+                .take(width)
+                .enumerate()
+            {
+                if tile == Tile::Tree {
+                    row[x / 64] |= 1 << (x % 64);
+                }
+            }
+            bits.extend(row);
+        }
+
         Self {
-            width: input.find('\n').unwrap(),
+            width,
             height: input.lines().count(),
-            tiles: input.chars().filter_map(Tile::from_char).collect(),
+            words_per_row,
+            bits,
+            edges: Edges::default(),
+        }
+    }
+
+    pub fn with_edges(self, edges: Edges) -> Self {
+        Self { edges, ..self }
+    }
+
+    pub fn edges(&self) -> Edges {
+        self.edges
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Every position visited by a run, starting from the top left. `x` isn't wrapped to the map's
+    /// width, except on a torus, where `y` is wrapped too.
+    ///
+    /// Panics if the slope doesn't go down, unless the map is a torus.
+    pub fn path(&self, delta_x: usize, delta_y: usize) -> Path<'_> {
+        assert!(
+            delta_y > 0 || self.edges == Edges::Torus,
+            "a run that doesn't go down never ends"
+        );
+
+        Path {
+            map: self,
+            delta_x,
+            delta_y,
+            x: 0,
+            y: 0,
+            visited: match self.edges {
+                Edges::Torus => vec![false; self.width * self.height],
+                _ => vec![],
+            },
+            end: None,
         }
     }
 
-    pub fn tile(&self, x: usize, y: usize) -> Tile {
-        assert!(y < self.height);
+    /// Follows a slope until the run ends.
+    pub fn run(&self, delta_x: usize, delta_y: usize) -> Run {
+        let mut path = self.path(delta_x, delta_y);
+        let mut squares = 0;
+        let mut trees = 0;
 
-        let trunc_x = x % self.width;
-        let i = y * self.width + trunc_x;
+        for (x, y) in &mut path {
+            squares += 1;
+            if self.tile(x, y) == Some(Tile::Tree) {
+                trees += 1;
+            }
+        }
 
-        self.tiles[i]
+        Run {
+            squares,
+            trees,
+            end: path.end().unwrap(),
+        }
+    }
+
+    fn is_tree(&self, x: usize, y: usize) -> bool {
+        self.bits[y * self.words_per_row + x / 64] >> (x % 64) & 1 == 1
+    }
+
+    /// The tile at `(x, y)`, or `None` if that's off the map.
+    pub fn tile(&self, x: usize, y: usize) -> Option<Tile> {
+        let (x, y) = match self.edges {
+            Edges::Wrap if y < self.height => (x % self.width, y),
+            Edges::Bounded if x < self.width && y < self.height => (x, y),
+            Edges::Mirrored if y < self.height => {
+                let x = x % (2 * self.width);
+                if x < self.width {
+                    (x, y)
+                } else {
+                    (2 * self.width - 1 - x, y)
+                }
+            }
+            Edges::Torus => (x % self.width, y % self.height),
+            _ => return None,
+        };
+
+        Some(if self.is_tree(x, y) {
+            Tile::Tree
+        } else {
+            Tile::Open
+        })
     }
 
+    /// The trees hit by a run down the slope, as in [`Map::run`].
     pub fn trees_on_slope(&self, delta_x: usize, delta_y: usize) -> u64 {
+        if self.edges != Edges::Wrap {
+            return self.run(delta_x, delta_y).trees;
+        }
+
+        // Reducing `delta_x` to less than the width means wrapping `x` never takes more than one
+        // subtraction.
+        let delta_x = delta_x % self.width;
         let mut x = 0;
-        let mut y = 0;
         let mut trees_encountered = 0;
 
-        while y < self.height {
-            if self.tile(x, y) == Tile::Tree {
+        for y in (0..self.height).step_by(delta_y) {
+            if self.is_tree(x, y) {
                 trees_encountered += 1;
             }
 
             x += delta_x;
-            y += delta_y;
+            if x >= self.width {
+                x -= self.width;
+            }
         }
 
         trees_encountered
     }
+
+    /// Counts the trees on each slope. With [`Edges::Wrap`], that's a single pass over the rows.
+    pub fn trees_on_slopes(&self, slopes: &[Slope]) -> Vec<u64> {
+        if self.edges != Edges::Wrap {
+            return slopes
+                .iter()
+                .map(|slope| self.trees_on_slope(slope.dx, slope.dy))
+                .collect();
+        }
+
+        struct Run {
+            slope: usize,
+            delta_x: usize,
+            x: usize,
+            trees: u64,
+        }
+
+        // Slopes with the same `dy` visit the same rows, so each row only has to check the groups
+        // that land on it.
+        let mut groups: Vec<(usize, Vec<Run>)> = Vec::new();
+        for (i, slope) in slopes.iter().enumerate() {
+            assert!(slope.dy > 0);
+
+            let run = Run {
+                slope: i,
+                delta_x: slope.dx % self.width,
+                x: 0,
+                trees: 0,
+            };
+            match groups.iter_mut().find(|(dy, _)| *dy == slope.dy) {
+                Some((_, runs)) => runs.push(run),
+                None => groups.push((slope.dy, vec![run])),
+            }
+        }
+
+        let mut next_y = vec![0; groups.len()];
+        for y in 0..self.height {
+            for ((dy, runs), next_y) in groups.iter_mut().zip(&mut next_y) {
+                if *next_y != y {
+                    continue;
+                }
+                *next_y += *dy;
+
+                for run in runs {
+                    // Trees are scattered at random, so a branch here would mostly mispredict.
+                    run.trees += u64::from(self.is_tree(run.x, y));
+
+                    run.x += run.delta_x;
+                    if run.x >= self.width {
+                        run.x -= self.width;
+                    }
+                }
+            }
+        }
+
+        let mut trees = vec![0; slopes.len()];
+        for run in groups.into_iter().flat_map(|(_, runs)| runs) {
+            trees[run.slope] = run.trees;
+        }
+        trees
+    }
+
+    /// Counts the trees on every slope with `dx` in `0..=max_dx` and `dy` in `1..=max_dy`, best
+    /// first by `objective`. Slopes with the same number of trees are in `(dx, dy)` order.
+    pub fn rank_slopes(
+        &self,
+        max_dx: usize,
+        max_dy: usize,
+        objective: Objective,
+    ) -> Vec<SlopeCount> {
+        // Where the map repeats, only `dx` modulo the period matters, so wider slopes reuse a
+        // narrower one's count.
+        let period = match self.edges {
+            Edges::Wrap | Edges::Torus => self.width,
+            Edges::Mirrored => 2 * self.width,
+            Edges::Bounded => usize::MAX,
+        };
+        let distinct_dx = period.min(max_dx.saturating_add(1));
+        let distinct_slopes: Vec<Slope> = (1..=max_dy)
+            .flat_map(|dy| (0..distinct_dx).map(move |dx| Slope { dx, dy }))
+            .collect();
+        let trees = self.trees_on_slopes(&distinct_slopes);
+
+        let mut ranked: Vec<SlopeCount> = (1..=max_dy)
+            .flat_map(|dy| (0..=max_dx).map(move |dx| Slope { dx, dy }))
+            .map(|slope| SlopeCount {
+                slope,
+                trees: trees[(slope.dy - 1) * distinct_dx + slope.dx % period],
+            })
+            .collect();
+
+        match objective {
+            Objective::FewestTrees => ranked.sort_by_key(|count| (count.trees, count.slope)),
+            Objective::MostTrees => ranked.sort_by_key(|count| (Reverse(count.trees), count.slope)),
+        }
+        ranked
+    }
+
+    /// The first slope from [`Map::rank_slopes`], or `None` if the bounds hold no slopes.
+    pub fn best_slope(
+        &self,
+        max_dx: usize,
+        max_dy: usize,
+        objective: Objective,
+    ) -> Option<SlopeCount> {
+        self.rank_slopes(max_dx, max_dy, objective)
+            .into_iter()
+            .next()
+    }
+}
+
+/// Iterator returned by [`Map::path`].
+#[derive(Debug, Clone)]
+pub struct Path<'a> {
+    map: &'a Map,
+    delta_x: usize,
+    delta_y: usize,
+    x: usize,
+    y: usize,
+    // Every square visited so far, only kept on a torus.
+    visited: Vec<bool>,
+    end: Option<RunEnd>,
+}
+
+impl Path<'_> {
+    /// Why the run stopped, once the iterator is exhausted.
+    pub fn end(&self) -> Option<RunEnd> {
+        self.end
+    }
+}
+
+impl Iterator for Path<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end.is_some() {
+            return None;
+        }
+
+        let map = self.map;
+        let (x, y) = (self.x, self.y);
+
+        self.end = match map.edges {
+            _ if map.edges != Edges::Torus && y >= map.height => Some(RunEnd::Bottom),
+            Edges::Bounded if x >= map.width => Some(RunEnd::Side),
+            Edges::Torus if self.visited[y * map.width + x] => Some(RunEnd::Cycle),
+            _ => None,
+        };
+        if self.end.is_some() {
+            return None;
+        }
+
+        if map.edges == Edges::Torus {
+            self.visited[y * map.width + x] = true;
+            self.x = (x + self.delta_x % map.width) % map.width;
+            self.y = (y + self.delta_y % map.height) % map.height;
+        } else {
+            self.x += self.delta_x;
+            self.y += self.delta_y;
+        }
+
+        Some((x, y))
+    }
 }
 
 pub fn part1(input: &str) -> u64 {
-    Map::from_str(input).trees_on_slope(1, 3)
+    Map::parse(input).trees_on_slope(3, 1)
 }
 
 pub fn part2(input: &str) -> u64 {
-    let map = Map::from_str(input);
+    let map = Map::parse(input);
 
-    [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+    let slopes: Vec<Slope> = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
         .iter()
-        .map(|(delta_x, delta_y)| map.trees_on_slope(*delta_x, *delta_y))
-        .product()
+        .map(|(dx, dy)| Slope { dx: *dx, dy: *dy })
+        .collect();
+
+    map.trees_on_slopes(&slopes).iter().product()
 }
@@ -0,0 +1,158 @@
+//! Draws a map with the paths of one or more slopes on top: `O` where a path crosses an open
+//! square and `X` where it hits a tree, in a different colour per slope. Unless the map is bounded
+//! or a torus, it's repeated to the right for as long as the longest path needs.
+
+use std::{collections::HashMap, fmt::Write};
+
+use super::{Map, Slope, Tile};
+
+// ANSI foreground colour codes, and the same colours for SVG.
+const PALETTE: [(u8, &str); 6] = [
+    (31, "#d62728"),
+    (32, "#2ca02c"),
+    (34, "#1f77b4"),
+    (33, "#ff7f0e"),
+    (35, "#9467bd"),
+    (36, "#17becf"),
+];
+
+const CELL: usize = 12;
+
+struct Overlay {
+    /// Columns to draw, a whole number of copies of the map.
+    width: usize,
+    /// The first slope, in the order given, to pass through each position.
+    hits: HashMap<(usize, usize), usize>,
+    trees: Vec<u64>,
+}
+
+impl Overlay {
+    fn new(map: &Map, slopes: &[Slope]) -> Self {
+        let mut hits = HashMap::new();
+        let mut trees = vec![0; slopes.len()];
+        let mut furthest = 0;
+
+        for (i, slope) in slopes.iter().enumerate() {
+            for (x, y) in map.path(slope.dx, slope.dy) {
+                hits.entry((x, y)).or_insert(i);
+                furthest = furthest.max(x);
+                if map.tile(x, y) == Some(Tile::Tree) {
+                    trees[i] += 1;
+                }
+            }
+        }
+
+        Self {
+            width: (furthest / map.width() + 1) * map.width(),
+            hits,
+            trees,
+        }
+    }
+
+    fn symbol(map: &Map, x: usize, y: usize, hit: bool) -> char {
+        match (map.tile(x, y) == Some(Tile::Tree), hit) {
+            (false, false) => '.',
+            (true, false) => '#',
+            (false, true) => 'O',
+            (true, true) => 'X',
+        }
+    }
+}
+
+/// Renders for a terminal, followed by a legend of how many trees each slope hits.
+pub fn ansi(map: &Map, slopes: &[Slope]) -> String {
+    let overlay = Overlay::new(map, slopes);
+    let mut out = String::new();
+
+    for y in 0..map.height() {
+        for x in 0..overlay.width {
+            let hit = overlay.hits.get(&(x, y));
+            let symbol = Overlay::symbol(map, x, y, hit.is_some());
+            match hit {
+                Some(i) => write!(
+                    out,
+                    "\x1b[1;{}m{}\x1b[0m",
+                    PALETTE[i % PALETTE.len()].0,
+                    symbol
+                ),
+                None => write!(out, "{}", symbol),
+            }
+            .unwrap();
+        }
+        out.push('\n');
+    }
+
+    for (i, slope) in slopes.iter().enumerate() {
+        writeln!(
+            out,
+            "\x1b[1;{}mO/X\x1b[0m right {}, down {}: {} trees",
+            PALETTE[i % PALETTE.len()].0,
+            slope.dx,
+            slope.dy,
+            overlay.trees[i]
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+/// Renders as a standalone SVG image, with untouched trees as circles and a legend underneath.
+pub fn svg(map: &Map, slopes: &[Slope]) -> String {
+    let overlay = Overlay::new(map, slopes);
+    let map_height = map.height() * CELL;
+    let width = overlay.width * CELL;
+    let height = map_height + (slopes.len() + 1) * CELL * 3 / 2;
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" font-family="monospace" font-size="{2}" text-anchor="middle">"#,
+        width, height, CELL
+    )
+    .unwrap();
+    writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+
+    for y in 0..map.height() {
+        for x in 0..overlay.width {
+            let (cx, cy) = (x * CELL + CELL / 2, y * CELL + CELL / 2);
+
+            match overlay.hits.get(&(x, y)) {
+                Some(i) => writeln!(
+                    out,
+                    r#"<text x="{}" y="{}" dy="0.35em" font-weight="bold" fill="{}">{}</text>"#,
+                    cx,
+                    cy,
+                    PALETTE[i % PALETTE.len()].1,
+                    Overlay::symbol(map, x, y, true)
+                ),
+                None if map.tile(x, y) == Some(Tile::Tree) => writeln!(
+                    out,
+                    r##"<circle cx="{}" cy="{}" r="{}" fill="#a3b18a"/>"##,
+                    cx,
+                    cy,
+                    CELL / 3
+                ),
+                None => continue,
+            }
+            .unwrap();
+        }
+    }
+
+    for (i, slope) in slopes.iter().enumerate() {
+        writeln!(
+            out,
+            r#"<text x="{}" y="{}" text-anchor="start" fill="{}">O/X right {}, down {}: {} trees</text>"#,
+            CELL / 2,
+            map_height + (i + 1) * CELL * 3 / 2,
+            PALETTE[i % PALETTE.len()].1,
+            slope.dx,
+            slope.dy,
+            overlay.trees[i]
+        )
+        .unwrap();
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
@@ -1,140 +1,521 @@
-use {itertools::Itertools, regex::Regex};
+use std::{collections::HashMap, convert::TryFrom, fmt};
+
+use crate::schema::{self, Schema};
 
 lazy_static::lazy_static! {
-    static ref HGT_REGEX: Regex = Regex::new("(?P<height>[0-9]{2,3})(?P<unit>in|cm)").unwrap();
-    static ref HCL_REGEX: Regex = Regex::new("#[0-9a-f]{6}").unwrap();
-}
-
-#[derive(Debug, Default)]
-pub struct PartialPassport {
-    byr: bool,
-    iyr: bool,
-    eyr: bool,
-    hgt: bool,
-    hcl: bool,
-    ecl: bool,
-    pid: bool,
-    cid: bool,
-}
-
-impl PartialPassport {
-    pub fn validate_line_lax(line: &str) -> bool {
-        let mut passport = Self::default();
-
-        for field in line.split(|c| c == ' ' || c == '\n') {
-            match field.split(':').collect_tuple().unwrap_or_default() {
-                ("byr", _) => passport.byr = true,
-                ("iyr", _) => passport.iyr = true,
-                ("eyr", _) => passport.eyr = true,
-                ("hgt", _) => passport.hgt = true,
-                ("hcl", _) => passport.hcl = true,
-                ("ecl", _) => passport.ecl = true,
-                ("pid", _) => passport.pid = true,
-                ("cid", _) => passport.cid = true,
-                ("", _) => (),
-                _ => unreachable!(),
-            }
+    pub static ref LAX: Schema =
+        Schema::from_toml(include_str!("../schemas/passport-lax.toml")).unwrap();
+    pub static ref STRICT: Schema =
+        Schema::from_toml(include_str!("../schemas/passport-strict.toml")).unwrap();
+}
+
+const FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Passport {
+    pub birth_year: u16,
+    pub issue_year: u16,
+    pub expiration_year: u16,
+    pub height: Height,
+    pub hair_color: Rgb,
+    pub eye_color: EyeColor,
+    /// Nine digits, kept as text so that leading zeros survive.
+    pub passport_id: String,
+    pub country_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Height {
+    Cm(u32),
+    In(u32),
+}
+
+impl Height {
+    /// Parses a number of digits followed by `cm` or `in`.
+    pub fn parse(s: &str) -> Option<Self> {
+        if let Some(cm) = s.strip_suffix("cm") {
+            schema::integer(cm).map(Self::Cm)
+        } else if let Some(inches) = s.strip_suffix("in") {
+            schema::integer(inches).map(Self::In)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Parses `#rrggbb`, in lowercase hex.
+    pub fn parse(s: &str) -> Option<Self> {
+        let hex = s.strip_prefix('#')?;
+        if hex.len() != 6 || !hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+            return None;
         }
 
-        passport.valid()
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Self {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        })
     }
+}
 
-    pub fn validate_line_strict(line: &str) -> bool {
-        let mut passport = Self::default();
-
-        for field in line.split(|c| c == ' ' || c == '\n') {
-            match field.split(':').collect_tuple().unwrap_or_default() {
-                ("byr", val) => {
-                    let val: u32 = val.parse().unwrap();
-                    if 1920 <= val && val <= 2002 {
-                        passport.byr = true;
-                    } else {
-                        return false;
-                    }
-                }
-                ("iyr", val) => {
-                    let val: u32 = val.parse().unwrap();
-                    if 2010 <= val && val <= 2020 {
-                        passport.iyr = true;
-                    } else {
-                        return false;
-                    }
-                }
-                ("eyr", val) => {
-                    let val: u32 = val.parse().unwrap();
-                    if 2020 <= val && val <= 2030 {
-                        passport.eyr = true;
-                    } else {
-                        return false;
-                    }
-                }
-                ("hgt", val) => {
-                    passport.hgt = if let Some((height, unit)) = HGT_REGEX
-                        .captures(val)
-                        .and_then(|caps| Some((caps.name("height")?, caps.name("unit")?)))
-                    {
-                        let height = height.as_str().parse::<u32>().unwrap();
-                        match unit.as_str() {
-                            "in" => 59 <= height && height <= 76,
-                            "cm" => 150 <= height && height <= 193,
-                            _ => return false,
-                        }
-                    } else {
-                        return false;
-                    };
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum EyeColor {
+    Amber,
+    Blue,
+    Brown,
+    Gray,
+    Green,
+    Hazel,
+    Other,
+}
 
-                    if !passport.hgt {
-                        return false;
-                    }
-                }
-                ("hcl", val) => {
-                    if HCL_REGEX.is_match(val) {
-                        passport.hcl = true
-                    } else {
-                        return false;
-                    }
-                }
-                ("ecl", val) => {
-                    if ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
-                        .iter()
-                        .any(|s| val == *s)
-                    {
-                        passport.ecl = true
-                    } else {
-                        return false;
-                    }
-                }
-                ("pid", val) => {
-                    if val.len() == 9 && val.chars().all(|c| c.is_digit(10)) {
-                        passport.pid = true
-                    } else {
-                        return false;
+impl EyeColor {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "amb" => Some(Self::Amber),
+            "blu" => Some(Self::Blue),
+            "brn" => Some(Self::Brown),
+            "gry" => Some(Self::Gray),
+            "grn" => Some(Self::Green),
+            "hzl" => Some(Self::Hazel),
+            "oth" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PassportError {
+    MissingField(&'static str),
+    InvalidValue {
+        field: &'static str,
+        value: String,
+    },
+    /// Something the policy says makes a record invalid.
+    Warning(Warning),
+}
+
+/// Something odd about a record that doesn't stop it being read.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Warning {
+    UnknownField(String),
+    /// Only the field's first value is used, but every value has to be valid.
+    DuplicateField(&'static str),
+    /// A token that isn't `key:value`, or has an empty key.
+    MalformedToken(String),
+}
+
+/// What to do about a kind of warning.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Treatment {
+    /// The record isn't a valid passport.
+    Invalid,
+    /// The record is judged on its other fields, but keeps the warning.
+    Ignore,
+    /// Stop reading records altogether.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Policy {
+    pub unknown_field: Treatment,
+    pub duplicate_field: Treatment,
+    pub malformed_token: Treatment,
+}
+
+impl Default for Policy {
+    /// Unknown and repeated fields make a record invalid, and malformed tokens are ignored.
+    fn default() -> Self {
+        Self {
+            unknown_field: Treatment::Invalid,
+            duplicate_field: Treatment::Invalid,
+            malformed_token: Treatment::Ignore,
+        }
+    }
+}
+
+impl Policy {
+    pub fn treatment(&self, warning: &Warning) -> Treatment {
+        match warning {
+            Warning::UnknownField(_) => self.unknown_field,
+            Warning::DuplicateField(_) => self.duplicate_field,
+            Warning::MalformedToken(_) => self.malformed_token,
+        }
+    }
+}
+
+/// A record read under a `Policy`, with every warning about it, whatever their treatment.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Checked {
+    pub passport: Result<Passport, Vec<PassportError>>,
+    pub warnings: Vec<Warning>,
+}
+
+impl Passport {
+    /// Builds a passport from one record of whitespace-separated `key:value` fields under the
+    /// default `Policy`, or lists everything wrong with it.
+    pub fn validate(record: &str) -> Result<Self, Vec<PassportError>> {
+        match Self::check(record, &Policy::default()) {
+            Ok(checked) => checked.passport,
+            Err(warning) => Err(vec![PassportError::Warning(warning)]),
+        }
+    }
+
+    /// Reads a record, collecting warnings instead of giving up on them. The errors for an invalid
+    /// passport are the warnings that make it invalid, in the order they appear, then missing
+    /// fields, then invalid values. Fails with the first warning the policy says is an error.
+    pub fn check(record: &str, policy: &Policy) -> Result<Checked, Warning> {
+        let mut fields = Fields {
+            values: HashMap::new(),
+            errors: Vec::new(),
+        };
+        let mut warnings = Vec::new();
+        let mut repeated = Vec::new();
+
+        for token in record.split_whitespace() {
+            let warning = match token.split_once(':') {
+                Some((key, value)) if !key.is_empty() => {
+                    match FIELDS.iter().find(|field| **field == key) {
+                        Some(key) if fields.values.contains_key(key) => {
+                            repeated.push((*key, value));
+                            Warning::DuplicateField(key)
+                        }
+                        Some(key) => {
+                            fields.values.insert(key, value);
+                            continue;
+                        }
+                        None => Warning::UnknownField(key.to_string()),
                     }
                 }
-                ("cid", _) => passport.cid = true,
-                ("", _) => (),
-                _ => unreachable!(),
+                // Not a field at all, or a value without a name.
+                _ => Warning::MalformedToken(token.to_string()),
+            };
+
+            match policy.treatment(&warning) {
+                Treatment::Invalid => fields.errors.push(PassportError::Warning(warning.clone())),
+                Treatment::Ignore => {}
+                Treatment::Error => return Err(warning),
+            }
+            warnings.push(warning);
+        }
+
+        for key in &FIELDS[..7] {
+            if !fields.values.contains_key(key) {
+                fields.errors.push(PassportError::MissingField(key));
+            }
+        }
+
+        // `STRICT` decides which values are valid, and these only turn them into their types.
+        let birth_year = fields.parse("byr", schema::integer);
+        let issue_year = fields.parse("iyr", schema::integer);
+        let expiration_year = fields.parse("eyr", schema::integer);
+        let height = fields.parse("hgt", Height::parse);
+        let hair_color = fields.parse("hcl", Rgb::parse);
+        let eye_color = fields.parse("ecl", EyeColor::parse);
+        let passport_id = fields.parse("pid", |pid| Some(pid.to_string()));
+        for (key, value) in repeated {
+            if !STRICT.accepts(key, value) {
+                fields.errors.push(PassportError::InvalidValue {
+                    field: key,
+                    value: value.to_string(),
+                });
             }
         }
 
-        passport.valid()
+        let passport = match (
+            birth_year,
+            issue_year,
+            expiration_year,
+            height,
+            hair_color,
+            eye_color,
+            passport_id,
+        ) {
+            (
+                Some(birth_year),
+                Some(issue_year),
+                Some(expiration_year),
+                Some(height),
+                Some(hair_color),
+                Some(eye_color),
+                Some(passport_id),
+            ) if fields.errors.is_empty() => Ok(Self {
+                birth_year,
+                issue_year,
+                expiration_year,
+                height,
+                hair_color,
+                eye_color,
+                passport_id,
+                country_id: fields.values.get("cid").map(|cid| cid.to_string()),
+            }),
+            _ => Err(fields.errors),
+        };
+
+        Ok(Checked { passport, warnings })
+    }
+}
+
+impl TryFrom<&str> for Passport {
+    type Error = PassportError;
+
+    /// Like `validate`, but stops at the first error. Every required field is checked for before
+    /// any value is, so a record with a missing field, and no unknown or repeated ones, always fails
+    /// with `MissingField`.
+    fn try_from(record: &str) -> Result<Self, Self::Error> {
+        Self::validate(record).map_err(|mut errors| errors.swap_remove(0))
+    }
+}
+
+struct Fields<'a> {
+    values: HashMap<&'static str, &'a str>,
+    errors: Vec<PassportError>,
+}
+
+impl Fields<'_> {
+    // The value of a field if it's present, `STRICT` accepts it and `parse` can read it. Only an
+    // invalid value is recorded as an error, as missing fields have already been.
+    fn parse<T>(&mut self, key: &'static str, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+        let value = *self.values.get(key)?;
+        let parsed = Some(value)
+            .filter(|value| STRICT.accepts(key, value))
+            .and_then(parse);
+        if parsed.is_none() {
+            self.errors.push(PassportError::InvalidValue {
+                field: key,
+                value: value.to_string(),
+            });
+        }
+        parsed
     }
+}
+
+/// A record that isn't a valid passport, and everything wrong with it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Rejection {
+    /// Which record this is, counting from 1.
+    pub record: usize,
+    /// The line the record starts on, counting from 1.
+    pub line: usize,
+    pub errors: Vec<PassportError>,
+}
 
-    pub fn valid(&self) -> bool {
-        self.byr && self.iyr && self.eyr && self.hgt && self.hcl && self.ecl && self.pid
+/// A warning about a record, where that record is.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RecordWarning {
+    /// Which record this is, counting from 1.
+    pub record: usize,
+    /// The line the record starts on, counting from 1.
+    pub line: usize,
+    pub warning: Warning,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Report {
+    pub valid: usize,
+    pub rejections: Vec<Rejection>,
+    /// Warnings the policy said to ignore, so they don't appear in any rejection.
+    pub ignored: Vec<RecordWarning>,
+}
+
+/// Validates every record in `input` under `policy`, skipping blank ones. Fails at the first
+/// warning the policy says is an error.
+pub fn report(input: &str, policy: &Policy) -> Result<Report, RecordWarning> {
+    let mut report = Report::default();
+    let mut line = 1;
+
+    for (i, record) in input.split("\n\n").enumerate() {
+        // Leading newlines belong to the blank line before the record, not the record itself.
+        let start = line + record.len() - record.trim_start_matches('\n').len();
+        line += record.matches('\n').count() + 2;
+
+        if record.trim().is_empty() {
+            continue;
+        }
+        let checked = Passport::check(record, policy).map_err(|warning| RecordWarning {
+            record: i + 1,
+            line: start,
+            warning,
+        })?;
+
+        report.ignored.extend(
+            checked
+                .warnings
+                .into_iter()
+                .filter(|warning| policy.treatment(warning) == Treatment::Ignore)
+                .map(|warning| RecordWarning {
+                    record: i + 1,
+                    line: start,
+                    warning,
+                }),
+        );
+        match checked.passport {
+            Ok(_) => report.valid += 1,
+            Err(errors) => report.rejections.push(Rejection {
+                record: i + 1,
+                line: start,
+                errors,
+            }),
+        }
     }
+
+    Ok(report)
+}
+
+/// Counts the records, separated by blank lines, that `schema` accepts.
+pub fn count_valid(input: &str, schema: &Schema) -> usize {
+    input
+        .split("\n\n")
+        .filter(|record| schema.validate(record))
+        .count()
 }
 
+/// Counts the records that have every required field, whatever their values and whatever else
+/// they have.
 pub fn part1(input: &str) -> usize {
     input
         .split("\n\n")
-        .filter(|line| PartialPassport::validate_line_lax(line))
+        .filter(|record| {
+            let keys: Vec<&str> = record
+                .split_whitespace()
+                .filter_map(|token| Some(token.split_once(':')?.0))
+                .collect();
+            FIELDS[..7].iter().all(|field| keys.contains(field))
+        })
         .count()
 }
 
+/// Counts the records that are valid passports. As in `STRICT`, a repeated field is fine as long
+/// as each of its values is valid.
 pub fn part2(input: &str) -> usize {
+    let policy = Policy {
+        duplicate_field: Treatment::Ignore,
+        ..Policy::default()
+    };
     input
         .split("\n\n")
-        .filter(|line| PartialPassport::validate_line_strict(line))
+        .filter(|record| {
+            Passport::check(record, &policy).is_ok_and(|checked| checked.passport.is_ok())
+        })
         .count()
 }
+
+impl fmt::Display for PassportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing {}", field),
+            Self::InvalidValue { field, value } => write!(f, "invalid {} {:?}", field, value),
+            Self::Warning(warning) => write!(f, "{}", warning),
+        }
+    }
+}
+
+impl std::error::Error for PassportError {}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField(field) => write!(f, "unknown field {:?}", field),
+            Self::DuplicateField(field) => write!(f, "{} appears more than once", field),
+            Self::MalformedToken(token) => write!(f, "malformed token {:?}", token),
+        }
+    }
+}
+
+impl fmt::Display for RecordWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "record {} (line {}): {}",
+            self.record, self.line, self.warning
+        )
+    }
+}
+
+impl std::error::Error for RecordWarning {}
+
+impl PassportError {
+    // What the summary groups rejections by, which leaves out the values themselves.
+    fn reason(&self) -> String {
+        match self {
+            Self::MissingField(field) => format!("missing {}", field),
+            Self::InvalidValue { field, .. } => field.to_string(),
+            Self::Warning(Warning::UnknownField(field)) => format!("unknown field {}", field),
+            Self::Warning(Warning::DuplicateField(field)) => format!("repeated {}", field),
+            Self::Warning(Warning::MalformedToken(_)) => "malformed token".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    /// One line per rejected record and per ignored warning, then a table of how many records were
+    /// rejected for each reason, most common first. A record with several errors counts towards
+    /// each of them.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut reasons = HashMap::new();
+
+        for rejection in &self.rejections {
+            let errors: Vec<String> = rejection.errors.iter().map(|e| e.to_string()).collect();
+            writeln!(
+                f,
+                "record {} (line {}): {}",
+                rejection.record,
+                rejection.line,
+                errors.join(", ")
+            )?;
+
+            let mut seen = Vec::new();
+            for error in &rejection.errors {
+                let reason = error.reason();
+                if !seen.contains(&reason) {
+                    *reasons.entry(reason.clone()).or_insert(0) += 1;
+                    seen.push(reason);
+                }
+            }
+        }
+
+        for ignored in &self.ignored {
+            writeln!(
+                f,
+                "record {} (line {}): ignored {}",
+                ignored.record, ignored.line, ignored.warning
+            )?;
+        }
+
+        let mut reasons: Vec<(String, usize)> = reasons.into_iter().collect();
+        reasons.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+        write!(
+            f,
+            "{} valid, {} rejected",
+            self.valid,
+            self.rejections.len()
+        )?;
+        if !self.ignored.is_empty() {
+            write!(f, ", {} warnings ignored", self.ignored.len())?;
+        }
+        writeln!(f)?;
+        if let Some((_, most)) = reasons.first() {
+            let width = most.to_string().len();
+            for (reason, count) in &reasons {
+                writeln!(
+                    f,
+                    "{:>width$} rejected for {}",
+                    count,
+                    reason,
+                    width = width
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
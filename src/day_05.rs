@@ -1,148 +1,232 @@
-use std::mem::MaybeUninit;
+pub mod seat_map;
 
-use itertools::Itertools;
+use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
-struct BoardingPass {
-    row: [FrontOrBack; 7],
-    col: [LeftOrRight; 3],
+/// How many bits of a boarding pass pick the row, and how many the column. The row bits come
+/// first, written `F` or `B`, then the column bits, written `L` or `R`, most significant first.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Layout {
+    row_bits: u32,
+    col_bits: u32,
 }
 
-impl BoardingPass {
-    fn from_line(line: &str) -> Self {
-        assert_eq!(line.len(), 10);
+impl Layout {
+    /// 128 rows of 8 seats, as in the puzzle.
+    pub const STANDARD: Self = Self {
+        row_bits: 7,
+        col_bits: 3,
+    };
+
+    /// Panics if seat IDs wouldn't fit in a `u32`.
+    pub fn new(row_bits: u32, col_bits: u32) -> Self {
+        assert!(
+            row_bits
+                .checked_add(col_bits)
+                .is_some_and(|bits| bits <= 32),
+            "{} row bits and {} column bits make seat IDs too big",
+            row_bits,
+            col_bits
+        );
+        Self { row_bits, col_bits }
+    }
 
-        let row = {
-            let mut data: [MaybeUninit<FrontOrBack>; 7] =
-                unsafe { MaybeUninit::uninit().assume_init() };
+    pub fn row_bits(&self) -> u32 {
+        self.row_bits
+    }
 
-            let mut num_initialized = 0;
-            for (i, c) in line.chars().take(7).enumerate() {
-                data[i] = MaybeUninit::new(FrontOrBack::from_char(c));
-                num_initialized += 1;
-            }
+    pub fn col_bits(&self) -> u32 {
+        self.col_bits
+    }
 
-            assert_eq!(num_initialized, 7);
-            unsafe { std::mem::transmute(data) }
-        };
+    pub fn rows(&self) -> u64 {
+        1 << self.row_bits
+    }
 
-        let col = {
-            let mut data: [MaybeUninit<LeftOrRight>; 3] =
-                unsafe { MaybeUninit::uninit().assume_init() };
+    pub fn cols(&self) -> u64 {
+        1 << self.col_bits
+    }
 
-            let mut num_initialized = 0;
-            for (i, c) in line.chars().skip(7).enumerate() {
-                data[i] = MaybeUninit::new(LeftOrRight::from_char(c));
-                num_initialized += 1;
-            }
+    /// `row * cols + col`, which is also the whole boarding pass read as one binary number.
+    pub fn seat_id(&self, seat: Seat) -> u32 {
+        ((seat.row as u64) << self.col_bits | seat.col as u64) as u32
+    }
 
-            assert_eq!(num_initialized, 3);
-            unsafe { std::mem::transmute(data) }
-        };
+    pub fn decode(&self, pass: &str) -> Result<Seat, DecodeError> {
+        let expected = (self.row_bits + self.col_bits) as usize;
+        let found = pass.chars().count();
+        if found != expected {
+            return Err(DecodeError::Length { expected, found });
+        }
+
+        let mut seat = Seat { row: 0, col: 0 };
+        for (i, c) in pass.chars().enumerate() {
+            let is_row = i < self.row_bits as usize;
+            let (target, bit) = match (c, is_row) {
+                ('F', true) => (&mut seat.row, 0),
+                ('B', true) => (&mut seat.row, 1),
+                ('L', false) => (&mut seat.col, 0),
+                ('R', false) => (&mut seat.col, 1),
+                _ => {
+                    return Err(DecodeError::InvalidChar {
+                        position: i + 1,
+                        found: c,
+                    })
+                }
+            };
+            *target = *target << 1 | bit;
+        }
+
+        Ok(seat)
+    }
+
+    /// The seat with a given ID, the inverse of `seat_id`.
+    pub fn seat(&self, id: u32) -> Result<Seat, EncodeError> {
+        let seats = self.rows() * self.cols();
+        if id as u64 >= seats {
+            return Err(EncodeError::SeatId { id, seats });
+        }
+
+        Ok(Seat {
+            row: (id as u64 >> self.col_bits) as u32,
+            col: (id as u64 & (self.cols() - 1)) as u32,
+        })
+    }
 
-        Self { row, col }
+    /// `seat_id`, for a seat that might not be on the aircraft. `seat_id` on its own lets a
+    /// column past the last one spill into the next row.
+    pub fn checked_seat_id(&self, seat: Seat) -> Result<u32, EncodeError> {
+        if seat.row as u64 >= self.rows() {
+            return Err(EncodeError::Row {
+                row: seat.row,
+                rows: self.rows(),
+            });
+        }
+        if seat.col as u64 >= self.cols() {
+            return Err(EncodeError::Col {
+                col: seat.col,
+                cols: self.cols(),
+            });
+        }
+
+        Ok(self.seat_id(seat))
     }
 
-    fn row(&self) -> u32 {
-        find_row(0, 127, &self.row)
+    /// The boarding pass for a seat, the inverse of `decode`.
+    pub fn encode(&self, seat: Seat) -> Result<String, EncodeError> {
+        self.checked_seat_id(seat)?;
+
+        let bits = |value: u32, count: u32, zero: char, one: char| {
+            (0..count)
+                .rev()
+                .map(move |i| if value >> i & 1 == 0 { zero } else { one })
+        };
+        Ok(bits(seat.row, self.row_bits, 'F', 'B')
+            .chain(bits(seat.col, self.col_bits, 'L', 'R'))
+            .collect())
     }
 
-    fn col(&self) -> u32 {
-        find_col(0, 7, &self.col)
+    pub fn encode_id(&self, id: u32) -> Result<String, EncodeError> {
+        self.encode(self.seat(id)?)
     }
+}
 
-    fn seat_id(&self) -> u32 {
-        8 * self.row() + self.col()
+impl Default for Layout {
+    fn default() -> Self {
+        Self::STANDARD
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum FrontOrBack {
-    Front,
-    Back,
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Seat {
+    pub row: u32,
+    pub col: u32,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DecodeError {
+    Length {
+        expected: usize,
+        found: usize,
+    },
+    /// `position` counts from 1.
+    InvalidChar {
+        position: usize,
+        found: char,
+    },
 }
 
-impl FrontOrBack {
-    fn from_char(c: char) -> Self {
-        match c {
-            'F' => Self::Front,
-            'B' => Self::Back,
-            _ => unreachable!(),
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length { expected, found } => {
+                write!(f, "expected {} characters, found {}", expected, found)
+            }
+            Self::InvalidChar { position, found } => {
+                write!(f, "unexpected {:?} at position {}", found, position)
+            }
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum LeftOrRight {
-    Left,
-    Right,
+impl std::error::Error for DecodeError {}
+
+/// A seat that isn't on the aircraft.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EncodeError {
+    Row { row: u32, rows: u64 },
+    Col { col: u32, cols: u64 },
+    SeatId { id: u32, seats: u64 },
 }
 
-impl LeftOrRight {
-    fn from_char(c: char) -> Self {
-        match c {
-            'L' => Self::Left,
-            'R' => Self::Right,
-            _ => unreachable!(),
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Row { row, rows } => write!(f, "row {} is past the last of {} rows", row, rows),
+            Self::Col { col, cols } => {
+                write!(f, "column {} is past the last of {} columns", col, cols)
+            }
+            Self::SeatId { id, seats } => {
+                write!(f, "seat ID {} is past the last of {} seats", id, seats)
+            }
         }
     }
 }
 
-// As of Rust 1.48.0, the tail call elimination optimization appears to be happening
-// https://rust.godbolt.org/z/Grfz4Y
-fn find_row(lower_bound: u32, upper_bound: u32, remaining: &[FrontOrBack]) -> u32 {
-    if lower_bound == upper_bound {
-        assert_eq!(remaining.len(), 0);
-        lower_bound
-    } else {
-        let (new_lower, new_upper) = match remaining[0] {
-            FrontOrBack::Front => (lower_bound, lower_bound + (upper_bound - lower_bound) / 2),
-            FrontOrBack::Back => (
-                lower_bound + (upper_bound - lower_bound) / 2 + 1,
-                upper_bound,
-            ),
-        };
-
-        find_row(new_lower, new_upper, &remaining[1..])
-    }
+impl std::error::Error for EncodeError {}
+
+/// The seat ID of every boarding pass in `input`, one per line.
+///
+/// Panics on a pass that doesn't fit `layout`.
+pub fn seat_ids(input: &str, layout: Layout) -> impl Iterator<Item = u32> + '_ {
+    input.lines().enumerate().map(move |(i, line)| {
+        let seat = layout
+            .decode(line)
+            .unwrap_or_else(|e| panic!("line {}: {}", i + 1, e));
+        layout.seat_id(seat)
+    })
 }
 
-fn find_col(lower_bound: u32, upper_bound: u32, remaining: &[LeftOrRight]) -> u32 {
-    if lower_bound == upper_bound {
-        assert_eq!(remaining.len(), 0);
-        lower_bound
-    } else {
-        let (new_lower, new_upper) = match remaining[0] {
-            LeftOrRight::Left => (lower_bound, lower_bound + (upper_bound - lower_bound) / 2),
-            LeftOrRight::Right => (
-                lower_bound + (upper_bound - lower_bound) / 2 + 1,
-                upper_bound,
-            ),
-        };
+pub fn highest_seat_id(input: &str, layout: Layout) -> Option<u32> {
+    seat_ids(input, layout).max()
+}
 
-        find_col(new_lower, new_upper, &remaining[1..])
-    }
+/// The one free seat whose neighbouring IDs are both taken, or the first if there are several.
+///
+/// This sorts the IDs on the passes rather than building a [`SeatMap`](seat_map::SeatMap), which
+/// would need a byte for every seat in the layout.
+pub fn missing_seat_id(input: &str, layout: Layout) -> Option<u32> {
+    let mut ids: Vec<u32> = seat_ids(input, layout).collect();
+    ids.sort_unstable();
+
+    ids.windows(2)
+        .find(|pair| pair[1] - pair[0] == 2)
+        .map(|pair| pair[0] + 1)
 }
 
 pub fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| BoardingPass::from_line(line).seat_id())
-        .max()
-        .unwrap()
+    highest_seat_id(input, Layout::STANDARD).unwrap()
 }
 
 pub fn part2(input: &str) -> Option<u32> {
-    let mut all_seats = input
-        .lines()
-        .map(|line| BoardingPass::from_line(line).seat_id())
-        .collect::<Vec<_>>();
-
-    all_seats.sort_unstable();
-
-    all_seats
-        .into_iter()
-        .tuple_windows()
-        .find(|(a, b)| *a == b - 2)
-        .map(|(a, _)| a + 1)
+    missing_seat_id(input, Layout::STANDARD)
 }
@@ -0,0 +1,152 @@
+//! Which seats on an aircraft are taken, by seat ID, and where the free ones are.
+
+use std::{fmt::Write, ops::Range};
+
+use super::{Layout, Seat};
+
+/// Every seat of a layout, one byte each, so it's only meant for cabins of a realistic size.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SeatMap {
+    layout: Layout,
+    taken: Vec<bool>,
+}
+
+/// Free seats next to each other in one row.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FreeRun {
+    pub row: u32,
+    pub cols: Range<u32>,
+}
+
+impl SeatMap {
+    pub fn new(layout: Layout) -> Self {
+        Self {
+            layout,
+            taken: vec![false; (layout.rows() * layout.cols()) as usize],
+        }
+    }
+
+    /// Panics if an ID isn't on the aircraft.
+    pub fn from_ids(layout: Layout, ids: impl IntoIterator<Item = u32>) -> Self {
+        let mut map = Self::new(layout);
+        for id in ids {
+            let seat = layout.seat(id).unwrap_or_else(|e| panic!("{}", e));
+            map.take(seat);
+        }
+        map
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Panics if the seat isn't on the aircraft.
+    pub fn take(&mut self, seat: Seat) {
+        let index = self.index(seat);
+        self.taken[index] = true;
+    }
+
+    /// Panics if the seat isn't on the aircraft.
+    pub fn is_taken(&self, seat: Seat) -> bool {
+        self.taken[self.index(seat)]
+    }
+
+    fn index(&self, seat: Seat) -> usize {
+        let id = self
+            .layout
+            .checked_seat_id(seat)
+            .unwrap_or_else(|e| panic!("{}", e));
+        id as usize
+    }
+
+    pub fn taken_count(&self) -> usize {
+        self.taken.iter().filter(|taken| **taken).count()
+    }
+
+    /// The IDs of the free seats, front to back.
+    pub fn free_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.taken
+            .iter()
+            .enumerate()
+            .filter(|(_, taken)| !**taken)
+            .map(|(id, _)| id as u32)
+    }
+
+    /// Free seats whose IDs either side are both taken, like the puzzle's own seat. Neighbouring
+    /// IDs can be in different rows.
+    pub fn free_between_taken(&self) -> impl Iterator<Item = u32> + '_ {
+        self.taken.windows(3).enumerate().filter_map(|(i, window)| {
+            if let [true, false, true] = window {
+                Some(i as u32 + 1)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The free IDs before the first taken seat, all of them if none are taken.
+    pub fn missing_front(&self) -> Range<u32> {
+        let end = self
+            .taken
+            .iter()
+            .position(|taken| *taken)
+            .unwrap_or(self.taken.len());
+        0..end as u32
+    }
+
+    /// The free IDs after the last taken seat, none if none are taken.
+    pub fn missing_back(&self) -> Range<u32> {
+        let start = self
+            .taken
+            .iter()
+            .rposition(|taken| *taken)
+            .map_or(self.taken.len(), |id| id + 1);
+        start as u32..self.taken.len() as u32
+    }
+
+    /// The longest run of free seats within one row, the front-most and then leftmost if there's
+    /// a tie.
+    pub fn longest_free_run(&self) -> Option<FreeRun> {
+        let cols = self.layout.cols() as usize;
+        let mut longest: Option<FreeRun> = None;
+
+        for (row, seats) in self.taken.chunks(cols).enumerate() {
+            let mut start = 0;
+            // A taken seat past the end, so that a run reaching the end of the row is closed too.
+            for (col, taken) in seats.iter().chain(std::iter::once(&true)).enumerate() {
+                if !taken {
+                    continue;
+                }
+                if col > start
+                    && longest
+                        .as_ref()
+                        .is_none_or(|run| run.cols.len() < col - start)
+                {
+                    longest = Some(FreeRun {
+                        row: row as u32,
+                        cols: start as u32..col as u32,
+                    });
+                }
+                start = col + 1;
+            }
+        }
+
+        longest
+    }
+
+    /// One line per row, front first, with its number and `#` for a taken seat or `.` for a free
+    /// one.
+    pub fn render(&self) -> String {
+        let cols = self.layout.cols() as usize;
+        let width = (self.layout.rows() - 1).to_string().len();
+        let mut out = String::new();
+
+        for (row, seats) in self.taken.chunks(cols).enumerate() {
+            write!(out, "{:>width$} ", row, width = width).unwrap();
+            out.extend(seats.iter().map(|taken| if *taken { '#' } else { '.' }));
+            out.push('\n');
+        }
+
+        out
+    }
+}
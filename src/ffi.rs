@@ -0,0 +1,110 @@
+//! C ABI over the solver registry. The header is generated into `include/aoc_2020.h` by the
+//! build script when the `ffi` feature is enabled.
+//!
+//! The crate only builds as a Rust library by default. Build a C library on demand with
+//! `cargo rustc --lib --release --features ffi --crate-type staticlib` (or `cdylib`).
+//!
+//! Ownership: every non-null `message` in an `AocAnswer` is allocated by this library and must be
+//! released exactly once with `aoc_string_free`. Input buffers are only borrowed for the duration
+//! of the call.
+
+use std::{ffi::CString, os::raw::c_char, ptr, slice, str};
+
+use crate::registry::{self, Answer, SolveError};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AocStatus {
+    /// `value` holds the answer.
+    Ok,
+    /// The solver ran to completion but found no answer for this input.
+    NoSolution,
+    UnknownDay,
+    UnknownPart,
+    /// The input pointer was null or the buffer was not valid UTF-8.
+    InvalidInput,
+    /// The part exists but has not been written yet.
+    Unimplemented,
+    /// The solver panicked, usually because the input was malformed. See `message`.
+    Panicked,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct AocAnswer {
+    pub status: AocStatus,
+    /// Only meaningful when `status` is `AOC_STATUS_OK`.
+    pub value: i64,
+    /// A human-readable description of the error, or null. Free with `aoc_string_free`.
+    pub message: *mut c_char,
+}
+
+impl AocAnswer {
+    fn error(status: AocStatus, message: impl ToString) -> Self {
+        // Interior NULs would truncate the message on the C side anyway.
+        let message = message.to_string().replace('\0', "");
+
+        Self {
+            status,
+            value: 0,
+            message: CString::new(message).unwrap().into_raw(),
+        }
+    }
+}
+
+/// Solves `part` (1 or 2) of `day` using the `len` bytes at `input` as the puzzle input.
+///
+/// # Safety
+///
+/// `input` must either be null or point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u32, part: u32, input: *const u8, len: usize) -> AocAnswer {
+    let bytes = if input.is_null() {
+        if len != 0 {
+            return AocAnswer::error(AocStatus::InvalidInput, "input is null");
+        }
+        &[]
+    } else {
+        slice::from_raw_parts(input, len)
+    };
+
+    let input = match str::from_utf8(bytes) {
+        Ok(input) => input,
+        Err(e) => return AocAnswer::error(AocStatus::InvalidInput, e),
+    };
+
+    match registry::solve(day, part, input) {
+        Ok(Answer::Number(value)) => AocAnswer {
+            status: AocStatus::Ok,
+            value,
+            message: ptr::null_mut(),
+        },
+        Ok(Answer::NoSolution) => AocAnswer {
+            status: AocStatus::NoSolution,
+            value: 0,
+            message: ptr::null_mut(),
+        },
+        Err(e) => {
+            let status = match e {
+                SolveError::UnknownDay(_) => AocStatus::UnknownDay,
+                SolveError::UnknownPart(_) => AocStatus::UnknownPart,
+                SolveError::Unimplemented => AocStatus::Unimplemented,
+                SolveError::Panicked(_) => AocStatus::Panicked,
+            };
+            AocAnswer::error(status, e)
+        }
+    }
+}
+
+/// Releases a string returned by this library. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `s` must be null or a pointer previously returned by this library that has not already been
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -8,3 +8,18 @@ pub mod day_07;
 pub mod day_08;
 pub mod day_09;
 pub mod day_10;
+
+pub mod answers;
+pub mod lint;
+pub mod progress;
+pub mod registry;
+pub mod schema;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "server")]
+pub mod server;
+
+#[cfg(feature = "tui")]
+pub mod dashboard;
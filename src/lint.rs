@@ -0,0 +1,291 @@
+use std::fmt;
+
+use regex::Regex;
+
+use crate::day_02::PasswordEntry;
+
+lazy_static::lazy_static! {
+    static ref RULE_REGEX: Regex = Regex::new(
+        r"^[a-z]+ [a-z]+ bags contain (no other bags|\d+ [a-z]+ [a-z]+ bags?(, \d+ [a-z]+ [a-z]+ bags?)*)\.$",
+    )
+    .unwrap();
+    static ref INSTRUCTION_REGEX: Regex = Regex::new(r"^(acc|jmp|nop) [+-]\d+$").unwrap();
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Problem {
+    /// 1-based, or `None` for problems with the file as a whole.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Layout {
+    /// One item per line, no blank lines.
+    Lines,
+    /// Groups of lines separated by exactly one blank line.
+    Groups,
+}
+
+struct Grammar {
+    layout: Layout,
+    /// Every problem with a single non-blank line.
+    line: fn(&str) -> Vec<String>,
+    /// Checks across lines, given each non-blank line with its line number.
+    file: fn(&[(usize, &str)]) -> Vec<Problem>,
+}
+
+fn grammar(day: u32) -> Option<Grammar> {
+    let no_file_checks = |_: &[(usize, &str)]| vec![];
+
+    let grammar = match day {
+        1 => Grammar {
+            layout: Layout::Lines,
+            line: |line| integer::<i64>(line),
+            file: no_file_checks,
+        },
+        2 => Grammar {
+            layout: Layout::Lines,
+            // Entries are free-form enough that anything simpler than the parser itself
+            // disagrees with it somewhere.
+            line: |line| match PasswordEntry::parse(line) {
+                Ok(_) => vec![],
+                Err(e) => vec![e.to_string()],
+            },
+            file: no_file_checks,
+        },
+        3 => Grammar {
+            layout: Layout::Lines,
+            line: |line| only(line, |c| c == '.' || c == '#', "'.' and '#'"),
+            file: same_width,
+        },
+        4 => Grammar {
+            layout: Layout::Groups,
+            line: passport_fields,
+            file: no_file_checks,
+        },
+        5 => Grammar {
+            layout: Layout::Lines,
+            line: boarding_pass,
+            file: no_file_checks,
+        },
+        6 => Grammar {
+            layout: Layout::Groups,
+            line: |line| only(line, |c| c.is_ascii_lowercase(), "'a' to 'z'"),
+            file: no_file_checks,
+        },
+        7 => Grammar {
+            layout: Layout::Lines,
+            line: |line| {
+                matches(
+                    &RULE_REGEX,
+                    line,
+                    "`<bag> bags contain <n> <bag> bag(s), ...` or `... contain no other bags.`",
+                )
+            },
+            file: no_file_checks,
+        },
+        8 => Grammar {
+            layout: Layout::Lines,
+            line: |line| matches(&INSTRUCTION_REGEX, line, "`acc|jmp|nop <+/-n>`"),
+            file: no_file_checks,
+        },
+        9 => Grammar {
+            layout: Layout::Lines,
+            line: |line| integer::<u64>(line),
+            file: |lines| {
+                // `day_09` needs the 25-number preamble plus at least one number to check.
+                if lines.len() <= 25 {
+                    vec![Problem {
+                        line: None,
+                        message: format!("expected more than 25 numbers, found {}", lines.len()),
+                    }]
+                } else {
+                    vec![]
+                }
+            },
+        },
+        10 => Grammar {
+            layout: Layout::Lines,
+            line: |line| integer::<u32>(line),
+            file: no_file_checks,
+        },
+        _ => return None,
+    };
+
+    Some(grammar)
+}
+
+/// Checks that `input` has the shape that `day` expects, without running its solver. Returns
+/// `None` if there is no grammar for `day`.
+pub fn lint(day: u32, input: &str) -> Option<Vec<Problem>> {
+    let grammar = grammar(day)?;
+    let mut problems = Vec::new();
+
+    if input.is_empty() {
+        problems.push(Problem {
+            line: None,
+            message: "file is empty".to_string(),
+        });
+        return Some(problems);
+    }
+
+    // `split` rather than `lines` so that a CR or blank line at the end of the file is still
+    // visible.
+    let mut lines: Vec<&str> = input.split('\n').collect();
+    let terminated = lines.last() == Some(&"");
+    if terminated {
+        lines.pop();
+    }
+
+    let mut content = Vec::with_capacity(lines.len());
+    let mut previous_blank = true;
+    for (i, raw_line) in lines.iter().enumerate() {
+        let line_number = i + 1;
+        let mut report = |message: String| {
+            problems.push(Problem {
+                line: Some(line_number),
+                message,
+            })
+        };
+
+        let line = match raw_line.strip_suffix('\r') {
+            Some(line) => {
+                report("line ends with CRLF instead of LF".to_string());
+                line
+            }
+            None => raw_line,
+        };
+
+        let trimmed = line.trim_end();
+        if trimmed.len() != line.len() {
+            report("trailing whitespace".to_string());
+        }
+
+        if trimmed.is_empty() {
+            match grammar.layout {
+                Layout::Lines => report("unexpected blank line".to_string()),
+                Layout::Groups if previous_blank => {
+                    report("blank line does not separate two groups".to_string())
+                }
+                Layout::Groups if i == lines.len() - 1 => {
+                    report("blank line at the end of the file".to_string())
+                }
+                Layout::Groups => (),
+            }
+            previous_blank = true;
+            continue;
+        }
+        previous_blank = false;
+
+        for message in (grammar.line)(trimmed) {
+            // A missing final line ending is fine on its own, but worth pointing out when the
+            // line it ends is also broken.
+            if !terminated && i == lines.len() - 1 {
+                report(format!(
+                    "{} (the last line has no line ending, so the file may be truncated)",
+                    message
+                ));
+            } else {
+                report(message);
+            }
+        }
+
+        content.push((line_number, trimmed));
+    }
+
+    problems.extend((grammar.file)(&content));
+    problems.sort_by_key(|problem| problem.line);
+
+    Some(problems)
+}
+
+fn integer<T: std::str::FromStr>(line: &str) -> Vec<String> {
+    match line.parse::<T>() {
+        Ok(_) => vec![],
+        Err(_) => vec![format!("expected an integer, found {:?}", line)],
+    }
+}
+
+fn matches(regex: &Regex, line: &str, expected: &str) -> Vec<String> {
+    if regex.is_match(line) {
+        vec![]
+    } else {
+        vec![format!("expected {}, found {:?}", expected, line)]
+    }
+}
+
+fn only(line: &str, allowed: fn(char) -> bool, expected: &str) -> Vec<String> {
+    match line.chars().find(|c| !allowed(*c)) {
+        Some(c) => vec![format!("unexpected {:?}, expected only {}", c, expected)],
+        None => vec![],
+    }
+}
+
+fn same_width(lines: &[(usize, &str)]) -> Vec<Problem> {
+    let width = match lines.first() {
+        Some((_, line)) => line.chars().count(),
+        None => return vec![],
+    };
+
+    lines
+        .iter()
+        .map(|(line_number, line)| (line_number, line.chars().count()))
+        .filter(|(_, line_width)| *line_width != width)
+        .map(|(line_number, line_width)| Problem {
+            line: Some(*line_number),
+            message: format!("row is {} wide, but the first row is {}", line_width, width),
+        })
+        .collect()
+}
+
+fn passport_fields(line: &str) -> Vec<String> {
+    const KNOWN: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+    let mut errors = Vec::new();
+
+    for token in line.split(' ') {
+        match token.split_once(':') {
+            Some((key, _)) if !KNOWN.contains(&key) => {
+                errors.push(format!("unknown field {:?}", key))
+            }
+            Some((key @ ("byr" | "iyr" | "eyr"), value)) if value.parse::<u32>().is_err() => {
+                errors.push(format!("{} should be a year, found {:?}", key, value))
+            }
+            Some(_) => (),
+            None => errors.push(format!("expected `<key>:<value>`, found {:?}", token)),
+        }
+    }
+
+    errors
+}
+
+fn boarding_pass(line: &str) -> Vec<String> {
+    let len = line.chars().count();
+    if len != 10 {
+        return vec![format!("expected 10 characters, found {}", len)];
+    }
+
+    line.chars()
+        .enumerate()
+        .filter_map(|(i, c)| match (i < 7, c) {
+            (true, 'F') | (true, 'B') | (false, 'L') | (false, 'R') => None,
+            (true, _) => Some(format!(
+                "unexpected {:?} in the row, expected 'F' or 'B'",
+                c
+            )),
+            (false, _) => Some(format!(
+                "unexpected {:?} in the column, expected 'L' or 'R'",
+                c
+            )),
+        })
+        .collect()
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
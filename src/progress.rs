@@ -0,0 +1,316 @@
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use itertools::Itertools;
+
+use crate::{
+    answers::KnownAnswers,
+    registry::{self, Answer, Day, PartStatus, SolveError, DAYS},
+};
+
+pub const DEFAULT_PATH: &str = "input/progress.txt";
+
+// Bump this whenever the file format changes, so an old file is rejected instead of misread.
+const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Stage {
+    /// The registry has no solver for the part yet.
+    Unimplemented,
+    /// The part runs, but hasn't produced an answer for the real input.
+    Implemented,
+    /// The part produced an answer that doesn't match the known one.
+    Wrong,
+    /// The part produced an answer, but there's no known answer to check it against.
+    Solved,
+    /// The part produced the known answer.
+    Verified,
+}
+
+impl Stage {
+    /// The stage shown by running a part against the day's real input.
+    pub fn from_result(
+        day: u32,
+        part: u32,
+        result: &Result<Answer, SolveError>,
+        known_answers: &KnownAnswers,
+    ) -> Self {
+        match result {
+            Ok(answer @ Answer::Number(_)) => match known_answers.matches(day, part, *answer) {
+                Some(true) => Self::Verified,
+                Some(false) => Self::Wrong,
+                None => Self::Solved,
+            },
+            Err(SolveError::Unimplemented) => Self::Unimplemented,
+            Ok(Answer::NoSolution) | Err(_) => Self::Implemented,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        Self::from_days_since_epoch((seconds / 86_400) as i64)
+    }
+
+    // Howard Hinnant's `civil_from_days`: http://howardhinnant.github.io/date_algorithms.html
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+        Self { year, month, day }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Record {
+    pub stage: Stage,
+    /// Kept even if the part later regresses.
+    pub first_solved: Option<Date>,
+}
+
+/// What we know about each `(day, part)`, persisted between runs.
+#[derive(Debug, Default)]
+pub struct Progress {
+    records: BTreeMap<(u32, u32), Record>,
+}
+
+impl Progress {
+    /// A missing file is treated as no progress yet.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => contents
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<Record> {
+        self.records.get(&(day, part)).copied()
+    }
+
+    /// Sets the stage of a part, and dates it the first time it's solved. A wrong answer doesn't
+    /// count as solved.
+    pub fn record(&mut self, day: u32, part: u32, stage: Stage, today: Date) {
+        let record = self.records.entry((day, part)).or_insert(Record {
+            stage,
+            first_solved: None,
+        });
+
+        record.stage = stage;
+        if stage >= Stage::Solved && record.first_solved.is_none() {
+            record.first_solved = Some(today);
+        }
+    }
+
+    /// Works out the stage of one part by running it against the day's input.
+    pub fn check(day: &Day, part: u32, known_answers: &KnownAnswers) -> Stage {
+        if day.status(part) == Some(PartStatus::Unimplemented) {
+            return Stage::Unimplemented;
+        }
+
+        let input = match std::fs::read_to_string(day.input_path()) {
+            Ok(input) => input,
+            Err(_) => return Stage::Implemented,
+        };
+
+        let result = registry::solve(day.number, part, &input);
+        Stage::from_result(day.number, part, &result, known_answers)
+    }
+
+    /// Re-checks every registered part and records the results.
+    pub fn update(&mut self, known_answers: &KnownAnswers, today: Date) {
+        for day in DAYS {
+            for part in 1..=2 {
+                let stage = Self::check(day, part, known_answers);
+                self.record(day.number, part, stage, today);
+            }
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.records
+            .values()
+            .filter(|record| record.stage == Stage::Verified)
+            .count()
+    }
+
+    /// A five-by-five grid of the 25 days, one symbol per part.
+    pub fn calendar(&self) -> String {
+        let mut calendar = format!("Advent of Code 2020: {} / 50 stars\n\n", self.stars());
+
+        for week in &(1..=25).chunks(5) {
+            let row = week
+                .map(|day| {
+                    let symbols: String = (1..=2)
+                        .map(
+                            |part| match self.get(day, part).map(|record| record.stage) {
+                                Some(Stage::Verified) => '*',
+                                Some(Stage::Solved) => '+',
+                                Some(Stage::Wrong) => 'x',
+                                Some(Stage::Implemented) => 'o',
+                                Some(Stage::Unimplemented) | None => '.',
+                            },
+                        )
+                        .collect();
+                    format!("{:>2} {}", day, symbols)
+                })
+                .join("   ");
+            calendar.push_str(&row);
+            calendar.push('\n');
+        }
+
+        calendar.push_str(
+            "\n* verified   + solved, unverified   x wrong   o implemented   . not started\n",
+        );
+        calendar
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, month, day) = s
+            .split('-')
+            .collect_tuple()
+            .ok_or_else(|| format!("expected a YYYY-MM-DD date, found {:?}", s))?;
+        let invalid = |_| format!("invalid date {:?}", s);
+
+        Ok(Self {
+            year: year.parse().map_err(invalid)?,
+            month: month.parse().map_err(invalid)?,
+            day: day.parse().map_err(invalid)?,
+        })
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Unimplemented => "unimplemented",
+            Self::Implemented => "implemented",
+            Self::Wrong => "wrong",
+            Self::Solved => "solved",
+            Self::Verified => "verified",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unimplemented" => Ok(Self::Unimplemented),
+            "implemented" => Ok(Self::Implemented),
+            "wrong" => Ok(Self::Wrong),
+            "solved" => Ok(Self::Solved),
+            "verified" => Ok(Self::Verified),
+            _ => Err(format!("unknown stage {:?}", s)),
+        }
+    }
+}
+
+// The file is a `version <n>` line followed by one `<day> <part> <stage> <first solved|->` line
+// per part.
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "version {}", VERSION)?;
+
+        for ((day, part), record) in &self.records {
+            let first_solved = record
+                .first_solved
+                .map_or_else(|| "-".to_string(), |date| date.to_string());
+            writeln!(f, "{} {} {} {}", day, part, record.stage, first_solved)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Progress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate();
+
+        match lines.next() {
+            Some((_, line)) if line == format!("version {}", VERSION) => (),
+            Some((_, line)) => {
+                return Err(format!(
+                    "unsupported progress file {:?}, expected `version {}`",
+                    line, VERSION
+                ))
+            }
+            None => return Ok(Self::default()),
+        }
+
+        let mut records = BTreeMap::new();
+        for (line_number, line) in lines {
+            let error = |message: String| format!("line {}: {}", line_number + 1, message);
+
+            let (day, part, stage, first_solved) =
+                line.split(' ').collect_tuple().ok_or_else(|| {
+                    error(format!(
+                        "expected `<day> <part> <stage> <first solved>`, found {:?}",
+                        line
+                    ))
+                })?;
+
+            let day = day
+                .parse()
+                .map_err(|_| error(format!("invalid day {:?}", day)))?;
+            let part = part
+                .parse()
+                .map_err(|_| error(format!("invalid part {:?}", part)))?;
+            let record = Record {
+                stage: stage.parse().map_err(error)?,
+                first_solved: match first_solved {
+                    "-" => None,
+                    date => Some(date.parse().map_err(error)?),
+                },
+            };
+
+            records.insert((day, part), record);
+        }
+
+        Ok(Self { records })
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    convert::TryFrom,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::Once,
+};
+
+use crate::*;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    NoSolution,
+}
+
+pub type Solver = fn(&str) -> Answer;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PartStatus {
+    Implemented,
+    Unimplemented,
+}
+
+pub struct Day {
+    pub number: u32,
+    /// `None` for a part that isn't implemented yet.
+    parts: [Option<Solver>; 2],
+}
+
+impl Day {
+    pub fn solver(&self, part: u32) -> Result<Solver, SolveError> {
+        match part {
+            1 | 2 => self.parts[part as usize - 1].ok_or(SolveError::Unimplemented),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        format!("input/day-{:02}.txt", self.number).into()
+    }
+
+    /// Whether a part is implemented, going by `DAYS` alone, so it's cheap and never runs a
+    /// solver. `None` if the part doesn't exist.
+    pub fn status(&self, part: u32) -> Option<PartStatus> {
+        match self.solver(part) {
+            Ok(_) => Some(PartStatus::Implemented),
+            Err(SolveError::Unimplemented) => Some(PartStatus::Unimplemented),
+            Err(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SolveError {
+    UnknownDay(u32),
+    UnknownPart(u32),
+    Unimplemented,
+    Panicked(String),
+}
+
+// Each day's `part1`/`part2` takes whatever input shape its binary produced, so the registry
+// adapts them all to take the raw input text.
+pub static DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        parts: [
+            Some(|input| day_01::part1(&parse_expenses(input)).into()),
+            Some(|input| day_01::part2(&parse_expenses(input)).into()),
+        ],
+    },
+    Day {
+        number: 2,
+        parts: [
+            Some(|input| day_02::part1(&parse_lines(input)).into()),
+            Some(|input| day_02::part2(&parse_lines(input)).into()),
+        ],
+    },
+    Day {
+        number: 3,
+        parts: [
+            Some(|input| day_03::part1(input).into()),
+            Some(|input| day_03::part2(input).into()),
+        ],
+    },
+    Day {
+        number: 4,
+        parts: [
+            Some(|input| day_04::part1(input).into()),
+            Some(|input| day_04::part2(input).into()),
+        ],
+    },
+    Day {
+        number: 5,
+        parts: [
+            Some(|input| day_05::part1(input).into()),
+            Some(|input| day_05::part2(input).into()),
+        ],
+    },
+    Day {
+        number: 6,
+        parts: [
+            Some(|input| day_06::part1(input).into()),
+            Some(|input| day_06::part2(input).into()),
+        ],
+    },
+    Day {
+        number: 7,
+        parts: [
+            Some(|input| day_07::part1(input).into()),
+            Some(|input| day_07::part2(input).into()),
+        ],
+    },
+    Day {
+        number: 8,
+        parts: [
+            Some(|input| day_08::part1(input).into()),
+            // Still `todo!()`.
+            None,
+        ],
+    },
+    Day {
+        number: 9,
+        parts: [
+            Some(|input| day_09::part1(input).into()),
+            Some(|input| day_09::part2(input).into()),
+        ],
+    },
+    Day {
+        number: 10,
+        parts: [
+            Some(|input| day_10::part1(input).into()),
+            Some(|input| day_10::part2(input).into()),
+        ],
+    },
+];
+
+fn parse_expenses(input: &str) -> Vec<i64> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+fn parse_lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+pub fn day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Runs one part of one day against `input`, turning any panic inside the solver into a
+/// `SolveError` instead of unwinding into the caller.
+pub fn solve(day_number: u32, part: u32, input: &str) -> Result<Answer, SolveError> {
+    let solver = day(day_number)
+        .ok_or(SolveError::UnknownDay(day_number))?
+        .solver(part)?;
+
+    catch_quiet(|| solver(input))
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+// The default panic hook prints every caught panic to stderr. Wrap it once so that panics
+// raised inside `catch_quiet` on the current thread are reported through the return value only.
+fn catch_quiet<T>(f: impl FnOnce() -> T) -> Result<T, SolveError> {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                default_hook(info);
+            }
+        }));
+    });
+
+    QUIET.with(|quiet| quiet.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.with(|quiet| quiet.set(false));
+
+    result.map_err(|payload| {
+        let message = panic_message(payload);
+        if message.starts_with("not yet implemented") {
+            SolveError::Unimplemented
+        } else {
+            SolveError::Panicked(message)
+        }
+    })
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Ok(message) = payload.downcast::<String>() {
+        *message
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Self::Number(n)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Self::Number(n.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Self::Number(n.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Self::Number(i64::try_from(n).expect("answer does not fit in an i64"))
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::Number(i64::try_from(n).expect("answer does not fit in an i64"))
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        answer.map_or(Self::NoSolution, Into::into)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{}", n),
+            Self::NoSolution => write!(f, "no solution"),
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownDay(day) => write!(f, "day {} is not registered", day),
+            Self::UnknownPart(part) => write!(f, "part {} does not exist", part),
+            Self::Unimplemented => write!(f, "not yet implemented"),
+            Self::Panicked(message) => write!(f, "solver panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
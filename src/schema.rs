@@ -0,0 +1,151 @@
+//! Declarative rules for documents made of whitespace-separated `key:value` fields, such as the
+//! passports in day 4.
+//!
+//! A schema is written in TOML, with one table per field:
+//!
+//! ```toml
+//! [fields.byr]
+//! required = true
+//! check = { type = "int", min = 1920, max = 2002, digits = 4 }
+//!
+//! [fields.hgt]
+//! required = true
+//! check = { type = "unit", units = { cm = [150, 193], in = [59, 76] } }
+//!
+//! [fields.cid]
+//! required = false
+//! ```
+//!
+//! A field without a `check` accepts any value. Integers are plain ASCII digits with an optional
+//! leading `-`, so `+1990` or ` 190` aren't integers. The checks are:
+//!
+//! - `int`: a decimal integer between `min` and `max`, inclusive, written with exactly `digits`
+//!   digits if that's given.
+//! - `regex`: the whole value matches `pattern`.
+//! - `enum`: the value is one of `values`.
+//! - `unit`: a decimal integer followed by one of the `units`, each with its own inclusive range.
+
+use std::{collections::BTreeMap, io, path::Path};
+
+use {
+    regex::Regex,
+    serde::{Deserialize, Deserializer},
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    pub fields: BTreeMap<String, Field>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Field {
+    pub required: bool,
+    #[serde(default)]
+    pub check: Option<Check>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Check {
+    Int {
+        min: i64,
+        max: i64,
+        #[serde(default)]
+        digits: Option<usize>,
+    },
+    Regex {
+        #[serde(deserialize_with = "whole_value_regex")]
+        pattern: Regex,
+    },
+    Enum {
+        values: Vec<String>,
+    },
+    Unit {
+        units: BTreeMap<String, (i64, i64)>,
+    },
+}
+
+/// Parses an integer written the way schemas expect: plain ASCII digits with an optional leading
+/// `-`. `str::parse` alone would also take a leading `+`.
+pub fn integer<T: std::str::FromStr>(s: &str) -> Option<T> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl Check {
+    pub fn accepts(&self, value: &str) -> bool {
+        let in_range = |number: &str, min: i64, max: i64| {
+            integer::<i64>(number).is_some_and(|number| min <= number && number <= max)
+        };
+
+        match self {
+            Self::Int { min, max, digits } => {
+                digits.is_none_or(|digits| value.trim_start_matches('-').len() == digits)
+                    && in_range(value, *min, *max)
+            }
+            Self::Regex { pattern } => pattern.is_match(value),
+            Self::Enum { values } => values.iter().any(|allowed| allowed == value),
+            Self::Unit { units } => units.iter().any(|(unit, (min, max))| {
+                value
+                    .strip_suffix(unit.as_str())
+                    .is_some_and(|number| in_range(number, *min, *max))
+            }),
+        }
+    }
+}
+
+// Patterns are anchored so that they have to match the whole value, not just part of it.
+fn whole_value_regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    Regex::new(&format!("^(?:{})$", pattern)).map_err(serde::de::Error::custom)
+}
+
+impl Schema {
+    pub fn from_toml(source: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(source)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        Self::from_toml(&source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Whether `key` is a known field and `value` passes its check.
+    pub fn accepts(&self, key: &str, value: &str) -> bool {
+        self.fields.get(key).is_some_and(|field| {
+            field
+                .check
+                .as_ref()
+                .is_none_or(|check| check.accepts(value))
+        })
+    }
+
+    /// Whether `document` has every required field, and every field it has is known and passes
+    /// its check.
+    pub fn validate(&self, document: &str) -> bool {
+        let mut present = Vec::new();
+
+        for token in document.split_whitespace() {
+            let (key, value) = match token.split_once(':') {
+                Some(field) => field,
+                // Not a field at all, so there's nothing to check.
+                None => continue,
+            };
+
+            if !self.accepts(key, value) {
+                return false;
+            }
+            present.push(key);
+        }
+
+        self.fields
+            .iter()
+            .filter(|(_, field)| field.required)
+            .all(|(key, _)| present.contains(&key.as_str()))
+    }
+}
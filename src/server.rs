@@ -0,0 +1,143 @@
+use std::time::{Duration, Instant};
+
+use {
+    axum::{
+        extract::{DefaultBodyLimit, Path, State},
+        http::StatusCode,
+        routing::{get, post},
+        Json, Router,
+    },
+    serde::Serialize,
+};
+
+use crate::registry::{self, Answer, PartStatus, SolveError, DAYS};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Larger request bodies are rejected with 413 before the solver sees them.
+    pub max_input_bytes: usize,
+    /// Requests whose solver hasn't finished by then get a 504.
+    pub timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_input_bytes: 1024 * 1024,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DayListing {
+    day: u32,
+    parts: Vec<PartListing>,
+}
+
+#[derive(Debug, Serialize)]
+struct PartListing {
+    part: u32,
+    status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Solution {
+    day: u32,
+    part: u32,
+    /// `null` when the solver finished without finding an answer.
+    answer: Option<i64>,
+    elapsed_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+type ErrorResponse = (StatusCode, Json<ErrorBody>);
+
+fn error(status: StatusCode, message: impl ToString) -> ErrorResponse {
+    (
+        status,
+        Json(ErrorBody {
+            error: message.to_string(),
+        }),
+    )
+}
+
+pub fn router(config: Config) -> Router {
+    Router::new()
+        .route("/days", get(list_days))
+        .route("/days/:day/parts/:part", post(solve))
+        .layer(DefaultBodyLimit::max(config.max_input_bytes))
+        .with_state(config)
+}
+
+async fn list_days() -> Json<Vec<DayListing>> {
+    let days = DAYS
+        .iter()
+        .map(|day| DayListing {
+            day: day.number,
+            parts: (1..=2)
+                .map(|part| PartListing {
+                    part,
+                    status: match day.status(part).unwrap() {
+                        PartStatus::Implemented => "implemented",
+                        PartStatus::Unimplemented => "unimplemented",
+                    },
+                })
+                .collect(),
+        })
+        .collect();
+
+    Json(days)
+}
+
+// Solvers are CPU-bound and can't be interrupted, so one that runs past the timeout keeps its
+// blocking thread until it finishes; the client just stops waiting for it.
+async fn run_solver<T: Send + 'static>(
+    config: Config,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, ErrorResponse> {
+    match tokio::time::timeout(config.timeout, tokio::task::spawn_blocking(f)).await {
+        Ok(joined) => joined.map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e)),
+        Err(_) => Err(error(
+            StatusCode::GATEWAY_TIMEOUT,
+            format!("solver did not finish within {:?}", config.timeout),
+        )),
+    }
+}
+
+async fn solve(
+    State(config): State<Config>,
+    Path((day, part)): Path<(u32, u32)>,
+    input: String,
+) -> Result<Json<Solution>, ErrorResponse> {
+    let (answer, elapsed) = run_solver(config, move || {
+        let start = Instant::now();
+        let answer = registry::solve(day, part, &input);
+        (answer, start.elapsed())
+    })
+    .await?;
+
+    let answer = match answer {
+        Ok(Answer::Number(n)) => Some(n),
+        Ok(Answer::NoSolution) => None,
+        Err(e) => {
+            let status = match e {
+                SolveError::UnknownDay(_) | SolveError::UnknownPart(_) => StatusCode::NOT_FOUND,
+                SolveError::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+                SolveError::Panicked(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            };
+            return Err(error(status, e));
+        }
+    };
+
+    Ok(Json(Solution {
+        day,
+        part,
+        answer,
+        elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+    }))
+}
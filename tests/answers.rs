@@ -0,0 +1,52 @@
+use aoc_2020::{answers::KnownAnswers, registry::Answer};
+
+#[test]
+fn parse() {
+    let answers = KnownAnswers::parse(
+        "# day part answer\n\
+         1 1 1006875\n\
+         \n\
+         \x20 1 2 165026160  \n\
+         # 2 1 999\n\
+         9 1 -4\n",
+    )
+    .unwrap();
+
+    assert_eq!(answers.get(1, 1), Some(1006875));
+    assert_eq!(answers.get(1, 2), Some(165026160));
+    assert_eq!(answers.get(9, 1), Some(-4));
+    assert_eq!(answers.get(2, 1), None);
+}
+
+#[test]
+fn malformed_lines() {
+    let error = |input| KnownAnswers::parse(input).unwrap_err();
+
+    assert_eq!(
+        error("1 1 5\n\n1 2\n"),
+        "line 3: expected `<day> <part> <answer>`, found \"1 2\""
+    );
+    assert_eq!(
+        error("1 1 5 6"),
+        "line 1: expected `<day> <part> <answer>`, found \"1 1 5 6\""
+    );
+    assert!(error("# one\n1 x 5").starts_with("line 2:"));
+    assert!(error("-1 1 5").starts_with("line 1:"));
+    assert!(error("1 1 five").starts_with("line 1:"));
+}
+
+#[test]
+fn matches() {
+    let answers = KnownAnswers::parse("3 1 299\n").unwrap();
+
+    assert_eq!(answers.matches(3, 1, Answer::Number(299)), Some(true));
+    assert_eq!(answers.matches(3, 1, Answer::Number(7)), Some(false));
+    assert_eq!(answers.matches(3, 1, Answer::NoSolution), Some(false));
+    assert_eq!(answers.matches(3, 2, Answer::Number(299)), None);
+}
+
+#[test]
+fn real_answers() {
+    let answers = KnownAnswers::load(aoc_2020::answers::DEFAULT_PATH).unwrap();
+    assert_eq!(answers.get(1, 1), Some(1006875));
+}
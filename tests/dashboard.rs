@@ -0,0 +1,88 @@
+#![cfg(feature = "tui")]
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_2020::{answers::KnownAnswers, dashboard::*, registry::Answer};
+
+// Updates until neither part of a day is running, or fails after a few seconds.
+fn settle(dashboard: &mut Dashboard, day_index: usize) {
+    let start = Instant::now();
+    while (1..=2).any(|part| matches!(dashboard.part(day_index, part), PartState::Running(_))) {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "runs never finished"
+        );
+        thread::sleep(Duration::from_millis(10));
+        dashboard.update();
+    }
+}
+
+#[test]
+fn solves_in_the_background() {
+    let mut dashboard = Dashboard::new(KnownAnswers::default(), Duration::from_secs(10));
+    assert!(matches!(dashboard.part(0, 1), PartState::NoInput));
+
+    dashboard.start(0);
+    assert!(matches!(dashboard.part(0, 1), PartState::Running(_)));
+    settle(&mut dashboard, 0);
+
+    assert!(matches!(
+        dashboard.part(0, 1),
+        PartState::Solved {
+            answer: Answer::Number(1006875),
+            ..
+        }
+    ));
+    assert!(matches!(
+        dashboard.part(0, 2),
+        PartState::Solved {
+            answer: Answer::Number(165026160),
+            ..
+        }
+    ));
+
+    // Day 8 part 2 hasn't been written.
+    dashboard.start(7);
+    settle(&mut dashboard, 7);
+    assert!(matches!(dashboard.part(7, 2), PartState::Unimplemented));
+}
+
+#[test]
+fn rerun_before_finishing() {
+    let mut dashboard = Dashboard::new(KnownAnswers::default(), Duration::from_secs(10));
+
+    dashboard.start(0);
+    dashboard.start(0);
+    settle(&mut dashboard, 0);
+    // Let the results of both runs arrive.
+    thread::sleep(Duration::from_millis(100));
+    dashboard.update();
+
+    assert!(matches!(
+        dashboard.part(0, 1),
+        PartState::Solved {
+            answer: Answer::Number(1006875),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn timeouts() {
+    let mut dashboard = Dashboard::new(KnownAnswers::default(), Duration::ZERO);
+
+    dashboard.start(0);
+    thread::sleep(Duration::from_millis(1));
+    dashboard.update();
+    assert!(matches!(dashboard.part(0, 1), PartState::TimedOut));
+    assert!(matches!(dashboard.part(0, 2), PartState::TimedOut));
+
+    // The solvers finish soon after, but they were already given up on.
+    thread::sleep(Duration::from_millis(200));
+    dashboard.update();
+    assert!(matches!(dashboard.part(0, 1), PartState::TimedOut));
+    assert!(matches!(dashboard.part(0, 2), PartState::TimedOut));
+}
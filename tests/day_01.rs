@@ -0,0 +1,137 @@
+use aoc_2020::day_01::*;
+
+#[test]
+fn real_input() {
+    let input: Vec<i64> = std::fs::read_to_string("input/day-01.txt")
+        .unwrap()
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect();
+
+    assert_eq!(part1(&input), Some(1006875));
+    assert_eq!(part2(&input), Some(165026160));
+}
+
+#[test]
+fn find_k_sum_any_k() {
+    let entries = [1721, 979, 366, 299, 675, 1456];
+
+    assert_eq!(find_k_sum(&entries, 0, 0), Some(vec![]));
+    assert_eq!(find_k_sum(&entries, 0, 5), None);
+    assert_eq!(find_k_sum(&entries, 1, 366), Some(vec![366]));
+    assert_eq!(find_k_sum(&entries, 2, 2020), Some(vec![299, 1721]));
+    assert_eq!(find_k_sum(&entries, 3, 2020), Some(vec![366, 675, 979]));
+    assert_eq!(
+        find_k_sum(&entries, 4, 2319),
+        Some(vec![299, 366, 675, 979])
+    );
+    assert_eq!(
+        find_k_sum(&entries, 6, 5496),
+        Some(vec![299, 366, 675, 979, 1456, 1721])
+    );
+}
+
+#[test]
+fn find_k_sum_no_solution() {
+    let entries = [1721, 979, 366, 299, 675, 1456];
+
+    assert_eq!(find_k_sum(&entries, 1, 300), None);
+    assert_eq!(find_k_sum(&entries, 2, 1), None);
+    assert_eq!(find_k_sum(&entries, 3, 100_000), None);
+    assert_eq!(find_k_sum(&entries, 4, 2320), None);
+    assert_eq!(find_k_sum(&entries, 7, 5496), None);
+    assert_eq!(find_k_sum(&[], 1, 0), None);
+}
+
+#[test]
+fn does_not_pair_an_entry_with_itself() {
+    assert_eq!(part1(&[1010, 1721, 979]), None);
+    assert_eq!(part2(&[1000, 20, 7]), None);
+    assert_eq!(find_k_sum(&[1, 2, 3], 3, 6), Some(vec![1, 2, 3]));
+    assert_eq!(find_k_sum(&[1, 2, 3], 3, 9), None);
+}
+
+#[test]
+fn duplicates_are_separate_entries() {
+    assert_eq!(part1(&[1010, 1721, 1010]), Some(1010 * 1010));
+    assert_eq!(part2(&[5, 2010, 299, 5]), Some(5 * 5 * 2010));
+    assert_eq!(part2(&[1000, 20, 1000]), Some(1000 * 1000 * 20));
+    assert_eq!(find_k_sum(&[7, 7, 7, 7], 4, 28), Some(vec![7, 7, 7, 7]));
+    assert_eq!(find_k_sum(&[7, 7, 7], 4, 28), None);
+}
+
+#[test]
+fn negative_entries() {
+    assert_eq!(part1(&[-100, 2120, 3]), Some(-100 * 2120));
+    assert_eq!(part2(&[-5, 4000, -1975, 1]), Some(-5 * 4000 * -1975));
+    assert_eq!(find_k_sum(&[-3, -2, -1, 0], 2, -5), Some(vec![-3, -2]));
+    assert_eq!(
+        find_k_sum(&[-3, 10, -7, 4, 1], 4, -5),
+        Some(vec![-7, -3, 1, 4])
+    );
+}
+
+#[test]
+fn all_combinations() {
+    let combinations: Vec<_> = k_sums(&[1010, 3, 1010, 2017, 1010], 2, 2020)
+        .map(|combination| combination.indices)
+        .collect();
+    assert_eq!(
+        combinations,
+        vec![vec![1, 3], vec![0, 2], vec![0, 4], vec![2, 4]]
+    );
+
+    assert_eq!(
+        summarize_k_sums(&[1010, 3, 1010, 2017, 1010], 2, 2020),
+        Solutions::Ambiguous(4)
+    );
+    assert_eq!(
+        summarize_k_sums(&[1721, 979, 366, 299, 675, 1456], 2, 2020),
+        Solutions::Unique(Combination {
+            indices: vec![3, 0],
+            values: vec![299, 1721],
+        })
+    );
+    assert_eq!(summarize_k_sums(&[1, 2], 2, 2020), Solutions::None);
+}
+
+#[test]
+fn subset_sum() {
+    let entries = [1721, 979, 366, 299, 675, 1456];
+    let config = SubsetSumConfig::default();
+
+    assert_eq!(
+        find_subset_sum(&entries, 2700, config).unwrap(),
+        Some(Combination {
+            indices: vec![0, 1],
+            values: vec![1721, 979],
+        })
+    );
+
+    // 979 + 366 + 299 + 1456 is the only subset that adds up to 3100.
+    let bounded = SubsetSumConfig {
+        max_size: 3,
+        ..config
+    };
+    let subset = find_subset_sum(&entries, 3100, config).unwrap().unwrap();
+    assert_eq!(subset.indices, vec![1, 2, 3, 5]);
+    assert_eq!(find_subset_sum(&entries, 3100, bounded).unwrap(), None);
+
+    // Only all four entries together add up to 6.
+    assert_eq!(
+        find_subset_sum(&[-5, 10, 3, -2], 6, config).unwrap(),
+        Some(Combination {
+            indices: vec![0, 1, 2, 3],
+            values: vec![-5, 10, 3, -2],
+        })
+    );
+
+    let small_table = SubsetSumConfig {
+        max_table_bytes: 1024,
+        ..config
+    };
+    assert!(matches!(
+        find_subset_sum(&entries, 2020, small_table),
+        Err(SubsetSumError::TableTooLarge { .. })
+    ));
+}
@@ -0,0 +1,322 @@
+use aoc_2020::day_02::*;
+use regex::Regex;
+
+fn lines(input: &str) -> Vec<String> {
+    input.lines().map(str::to_string).collect()
+}
+
+#[test]
+fn real_input() {
+    let input = lines(&std::fs::read_to_string("input/day-02.txt").unwrap());
+
+    assert_eq!(part1(&input), 434);
+    assert_eq!(part2(&input), 509);
+}
+
+#[test]
+fn parse() {
+    let line = "1-3 é: aéb";
+    let entry = PasswordEntry::parse(line).unwrap();
+    assert_eq!(entry.required, 'é');
+    assert_eq!(entry.password, "aéb");
+    assert_eq!(entry.char_at(3), Ok('b'));
+    assert_eq!(entry.char_at(0), Err(PositionError::Zero));
+    assert_eq!(
+        entry.char_at(4),
+        Err(PositionError::PastEnd {
+            position: 4,
+            len: 3
+        })
+    );
+
+    assert_eq!(
+        PasswordEntry::parse("1-3 a abc"),
+        Err(ParseError::Malformed)
+    );
+    assert_eq!(
+        PasswordEntry::parse("1-x a: abc"),
+        Err(ParseError::InvalidBound("x".to_string()))
+    );
+    assert_eq!(
+        PasswordEntry::parse("1-3 ab: abc"),
+        Err(ParseError::InvalidLetter("ab".to_string()))
+    );
+    assert_eq!(
+        PasswordEntry::parse("1-3 a: "),
+        Err(ParseError::EmptyPassword)
+    );
+}
+
+#[test]
+fn report() {
+    let input = lines("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n0-2 c: cc");
+
+    let part1 = aoc_2020::day_02::report(&input, &CountInRange);
+    assert_eq!((part1.valid, part1.invalid), (3, 1));
+    assert_eq!(part1.failures[0].line, 2);
+    assert_eq!(
+        part1.failures[0].violations[0].reason,
+        "'b' appears 0 times, need 1–3"
+    );
+
+    let part2 = aoc_2020::day_02::report(&input, &PositionXor);
+    let reasons: Vec<_> = part2
+        .failures
+        .iter()
+        .map(|failure| (failure.line, failure.violations[0].reason.as_str()))
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            (2, "positions 1 and 3 both lack 'b'"),
+            (3, "positions 2 and 9 both contain 'c'"),
+            (4, "position 0 is out of range, positions start at 1"),
+        ]
+    );
+}
+
+#[test]
+fn report_json() {
+    let input = lines("1-3 a: abcde\n1-3 b: cdefg\n1-3 a abc");
+    let report = aoc_2020::day_02::report(&input, &CountInRange);
+
+    assert_eq!(
+        serde_json::to_value(&report).unwrap(),
+        serde_json::json!({
+            "valid": 1,
+            "invalid": 2,
+            "failures": [
+                {
+                    "line": 2,
+                    "entry": "1-3 b: cdefg",
+                    "violations": [{ "policy": "count", "reason": "'b' appears 0 times, need 1–3" }],
+                },
+                {
+                    "line": 3,
+                    "entry": "1-3 a abc",
+                    "violations": [{ "policy": "parse", "reason": ParseError::Malformed.to_string() }],
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn policies() {
+    let entry = PasswordEntry::parse("1-3 a: ab-1é").unwrap();
+    let reasons = |policy: &dyn PasswordPolicy| {
+        policy
+            .violations(&entry)
+            .into_iter()
+            .map(|violation| format!("{}: {}", violation.policy, violation.reason))
+            .collect::<Vec<_>>()
+    };
+
+    assert!(reasons(&CountInRange).is_empty());
+    assert!(reasons(&PositionXor).is_empty());
+    assert!(reasons(&Length { min: 5, max: 5 }).is_empty());
+    assert_eq!(
+        reasons(&Length { min: 6, max: 8 }),
+        ["length: password is 5 characters long, need 6–8"]
+    );
+    assert_eq!(
+        reasons(&LetterCount {
+            letter: 'b',
+            min: 2,
+            max: 3
+        }),
+        ["count: 'b' appears 1 times, need 2–3"]
+    );
+
+    let char_at = |position, expected| CharAt { position, expected };
+    assert!(reasons(&char_at(1, CharTest::EntryLetter)).is_empty());
+    assert!(reasons(&char_at(5, CharTest::Char('é'))).is_empty());
+    assert!(reasons(&char_at(4, CharTest::Class(CharClass::Digit))).is_empty());
+    assert_eq!(
+        reasons(&char_at(2, CharTest::EntryLetter)),
+        ["position: position 2 is 'b', need 'a'"]
+    );
+    assert_eq!(
+        reasons(&char_at(3, CharTest::Class(CharClass::Uppercase))),
+        ["position: position 3 is '-', need a uppercase character"]
+    );
+    assert_eq!(
+        reasons(&char_at(6, CharTest::Char('x'))),
+        ["position: position 6 is past the end of a 5-character password"]
+    );
+
+    assert!(reasons(&RequiredClasses(vec![
+        CharClass::Lowercase,
+        CharClass::Symbol
+    ]))
+    .is_empty());
+    assert_eq!(
+        reasons(&RequiredClasses(vec![
+            CharClass::Uppercase,
+            CharClass::Digit,
+            CharClass::Symbol
+        ])),
+        ["classes: no uppercase character"]
+    );
+
+    assert!(reasons(&ForbiddenSubstrings(vec!["ba".to_string()])).is_empty());
+    assert_eq!(
+        reasons(&ForbiddenSubstrings(vec![
+            "ab".to_string(),
+            "xy".to_string(),
+            "1é".to_string()
+        ])),
+        ["forbidden: contains \"ab\"", "forbidden: contains \"1é\""]
+    );
+
+    assert!(reasons(&RegexMatch(Regex::new(r"\d").unwrap())).is_empty());
+    assert_eq!(
+        reasons(&RegexMatch(Regex::new("^[a-z]+$").unwrap())),
+        ["regex: does not match /^[a-z]+$/"]
+    );
+}
+
+#[test]
+fn combined_policies() {
+    let entry = PasswordEntry::parse("1-3 a: abc").unwrap();
+    let length = |min, max| -> Box<dyn PasswordPolicy> { Box::new(Length { min, max }) };
+    let reasons = |policy: &dyn PasswordPolicy| {
+        policy
+            .violations(&entry)
+            .into_iter()
+            .map(|violation| format!("{}: {}", violation.policy, violation.reason))
+            .collect::<Vec<_>>()
+    };
+
+    assert!(reasons(&All(vec![])).is_empty());
+    assert!(reasons(&All(vec![length(1, 3), Box::new(CountInRange)])).is_empty());
+    assert_eq!(
+        reasons(&All(vec![
+            length(1, 2),
+            Box::new(CountInRange),
+            length(4, 5)
+        ])),
+        [
+            "length: password is 3 characters long, need 1–2",
+            "length: password is 3 characters long, need 4–5",
+        ]
+    );
+
+    assert!(reasons(&Any(vec![length(1, 2), length(3, 3)])).is_empty());
+    assert_eq!(
+        reasons(&Any(vec![length(1, 2), length(4, 5)])),
+        [
+            "any: none of the alternatives hold (length: password is 3 characters long, need 1–2; \
+          length: password is 3 characters long, need 4–5)"
+        ]
+    );
+
+    let not = |policy, description: &str| Not {
+        policy,
+        description: description.to_string(),
+    };
+    assert!(reasons(&not(length(1, 2), "length 1-2")).is_empty());
+    assert_eq!(
+        reasons(&not(length(3, 3), "length 3-3")),
+        ["not: length 3-3 holds, but shouldn't"]
+    );
+}
+
+#[test]
+fn policy_file() {
+    let input = lines("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-2 x: 9xy");
+    let count = |source: &str| count_valid(&input, policy_file::parse(source).unwrap().as_ref());
+
+    assert_eq!(count("count"), 3);
+    assert_eq!(count("positions # part 2"), 2);
+    assert_eq!(count("count and positions"), 2);
+    assert_eq!(count("count or\npositions"), 3);
+    assert_eq!(count("not count"), 1);
+    assert_eq!(count("length 1-3 or has digit"), 1);
+    assert_eq!(count("count 'c' 1-9 or position 2 is letter"), 4);
+    assert_eq!(count("(count or positions) and not position 1 is digit"), 2);
+    assert_eq!(count("position 1 is 'a' or position 1 is uppercase"), 1);
+    assert_eq!(count(r#"forbid "cc""#), 3);
+    assert_eq!(count(r#"not forbid "x" and count"#), 1);
+    assert_eq!(count("matches /^[a-e]+$/"), 2);
+    assert_eq!(count(r"matches /\d/ or matches /c\/?d/"), 3);
+
+    let policy = policy_file::parse(r#"forbid "d" or matches /^a\/b$/"#).unwrap();
+    let report = aoc_2020::day_02::report(&lines("1-1 a: abcd"), policy.as_ref());
+    assert_eq!(
+        report.failures[0].violations[0].reason,
+        r#"none of the alternatives hold (forbidden: contains "d"; regex: does not match /^a/b$/)"#
+    );
+
+    let policy = policy_file::parse(r"not matches /a\/b/").unwrap();
+    let report = aoc_2020::day_02::report(&lines("1-1 a: a/b"), policy.as_ref());
+    assert_eq!(
+        report.failures[0].violations[0].reason,
+        r"`matches /a\/b/` holds, but shouldn't"
+    );
+}
+
+#[test]
+fn policy_file_errors() {
+    let error = |source: &str| {
+        let e = policy_file::parse(source).err().unwrap();
+        (e.line, e.column, e.message)
+    };
+
+    assert_eq!(
+        error("count and"),
+        (
+            1,
+            10,
+            "expected a constraint, found the end of the file".to_string()
+        )
+    );
+    assert_eq!(
+        error("count\n  or length 5-3"),
+        (2, 13, "the range 5-3 is empty".to_string())
+    );
+    assert_eq!(
+        error("# comment\nposition 0 is letter"),
+        (2, 10, "positions start at 1".to_string())
+    );
+    assert_eq!(
+        error("has vowel").2,
+        "expected `lowercase`, `uppercase`, `digit` or `symbol`, found `vowel`"
+    );
+    assert_eq!(
+        error("(count or positions"),
+        (1, 20, "expected `)`, found the end of the file".to_string())
+    );
+    assert_eq!(
+        error("count positions"),
+        (
+            1,
+            7,
+            "expected `and`, `or` or the end of the file, found `positions`".to_string()
+        )
+    );
+    assert_eq!(error("count % 2"), (1, 7, "unexpected '%'".to_string()));
+    assert_eq!(
+        error("forbid \"ab\ncount"),
+        (1, 8, "unterminated string".to_string())
+    );
+    assert_eq!(
+        error(r"matches /a\/"),
+        (1, 9, "unterminated regex".to_string())
+    );
+    assert_eq!(
+        error(r#"count or forbid """#),
+        (1, 17, "every password contains \"\"".to_string())
+    );
+    assert_eq!(
+        error("forbid /ab/"),
+        (1, 8, "expected a string, found `/ab/`".to_string())
+    );
+    assert_eq!(
+        error(r#"matches "ab""#),
+        (1, 9, "expected a regex, found `\"ab\"`".to_string())
+    );
+    let (line, column, message) = error("matches /a(b/");
+    assert_eq!((line, column), (1, 9));
+    assert!(message.starts_with("regex parse error"), "{}", message);
+}
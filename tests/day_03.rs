@@ -0,0 +1,215 @@
+use aoc_2020::day_03::*;
+
+const EXAMPLE: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+
+#[test]
+fn real_input() {
+    let input = std::fs::read_to_string("input/day-03.txt").unwrap();
+
+    assert_eq!(part1(&input), 299);
+    assert_eq!(part2(&input), 3621285278);
+}
+
+#[test]
+fn example() {
+    assert_eq!(part1(EXAMPLE), 7);
+    assert_eq!(part2(EXAMPLE), 336);
+}
+
+#[test]
+fn rank_slopes() {
+    let map = Map::parse(EXAMPLE);
+
+    let ranked = map.rank_slopes(25, 12, Objective::MostTrees);
+    assert_eq!(ranked.len(), 26 * 12);
+    for count in &ranked {
+        let trees = map
+            .path(count.slope.dx, count.slope.dy)
+            .filter(|(x, y)| map.tile(*x, *y) == Some(Tile::Tree))
+            .count();
+        assert_eq!(count.trees, trees as u64);
+    }
+    assert!(ranked.windows(2).all(|pair| {
+        (std::cmp::Reverse(pair[0].trees), pair[0].slope)
+            < (std::cmp::Reverse(pair[1].trees), pair[1].slope)
+    }));
+
+    // `dx` wraps around the 11-wide map, so the best slope ties with the same slope plus 11.
+    let best = map.best_slope(25, 12, Objective::MostTrees).unwrap();
+    assert_eq!(ranked[1].slope.dx, best.slope.dx + 11);
+    assert_eq!(ranked[1].trees, best.trees);
+
+    let fewest = map.best_slope(25, 12, Objective::FewestTrees).unwrap();
+    assert_eq!(fewest.trees, 0);
+    assert_eq!(map.best_slope(25, 0, Objective::FewestTrees), None);
+}
+
+#[test]
+fn wide_rows() {
+    // Rows wider than 64 squares span several words of the bitset.
+    let row: String = (0..150)
+        .map(|x| if x % 7 == 0 { '#' } else { '.' })
+        .collect();
+    let input = format!("{}\n", row).repeat(300);
+    let map = Map::parse(&input);
+
+    let slopes: Vec<Slope> = (0..160)
+        .flat_map(|dx| (1..4).map(move |dy| Slope { dx, dy }))
+        .collect();
+    let batched = map.trees_on_slopes(&slopes);
+    for (slope, trees) in slopes.iter().zip(batched) {
+        let expected = map
+            .path(slope.dx, slope.dy)
+            .filter(|(x, _)| x % 150 % 7 == 0)
+            .count();
+        assert_eq!(trees, expected as u64, "{:?}", slope);
+        assert_eq!(trees, map.trees_on_slope(slope.dx, slope.dy));
+    }
+}
+
+#[test]
+fn ragged_rows() {
+    // The first row sets the width, whatever the length of the others.
+    let map = Map::parse(
+        "..#\n#..#..##\n.\n..#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#\n",
+    );
+    assert_eq!(map.width(), 3);
+    let rows: Vec<String> = (0..4)
+        .map(|y| {
+            (0..4)
+                .map(|x| match map.tile(x, y).unwrap() {
+                    Tile::Open => '.',
+                    Tile::Tree => '#',
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(rows, ["..#.", "#..#", "....", "..#."]);
+    assert_eq!(map.trees_on_slope(3, 1), 1);
+    assert_eq!(map.trees_on_slopes(&[Slope { dx: 3, dy: 1 }]), [1]);
+}
+
+#[test]
+fn edges() {
+    let wrap = Map::parse(EXAMPLE);
+    assert_eq!(wrap.edges(), Edges::Wrap);
+    assert_eq!(wrap.tile(14, 2), Some(Tile::Open));
+    assert_eq!(wrap.tile(0, 11), None);
+    assert_eq!(
+        wrap.run(3, 1),
+        Run {
+            squares: 11,
+            trees: 7,
+            end: RunEnd::Bottom
+        }
+    );
+
+    let bounded = Map::parse(EXAMPLE).with_edges(Edges::Bounded);
+    assert_eq!(bounded.tile(10, 1), Some(Tile::Open));
+    assert_eq!(bounded.tile(11, 1), None);
+    assert_eq!(
+        bounded.run(3, 1),
+        Run {
+            squares: 4,
+            trees: 1,
+            end: RunEnd::Side
+        }
+    );
+    assert_eq!(bounded.run(1, 2).end, RunEnd::Bottom);
+    assert_eq!(bounded.trees_on_slope(1, 2), 2);
+
+    let mirrored = Map::parse(EXAMPLE).with_edges(Edges::Mirrored);
+    assert_eq!(mirrored.tile(11, 1), Some(Tile::Open));
+    assert_eq!(mirrored.tile(21, 1), Some(Tile::Tree));
+    assert_eq!(mirrored.tile(22, 1), Some(Tile::Tree));
+    assert_eq!(mirrored.tile(12, 2), Some(Tile::Tree));
+    let slopes = [
+        Slope { dx: 3, dy: 1 },
+        Slope { dx: 1, dy: 1 },
+        Slope { dx: 7, dy: 1 },
+        Slope { dx: 5, dy: 2 },
+    ];
+    assert_eq!(mirrored.trees_on_slopes(&slopes), [6, 2, 3, 0]);
+
+    let torus = Map::parse(EXAMPLE).with_edges(Edges::Torus);
+    assert_eq!(torus.tile(14, 13), wrap.tile(3, 2));
+    assert_eq!(
+        torus.run(3, 1),
+        Run {
+            squares: 11,
+            trees: 7,
+            end: RunEnd::Cycle
+        }
+    );
+    assert_eq!(torus.run(0, 0).squares, 1);
+    assert_eq!(torus.run(1, 0).squares, 11);
+
+    // The run only repeats once both coordinates do, after lcm(4, 6) steps.
+    let torus = Map::parse("#...\n.#..\n..#.\n...#\n....\n....\n").with_edges(Edges::Torus);
+    assert_eq!(
+        torus.run(1, 1),
+        Run {
+            squares: 12,
+            trees: 4,
+            end: RunEnd::Cycle
+        }
+    );
+    assert_eq!(torus.run(2, 3).squares, 2);
+    assert_eq!(torus.path(1, 1).nth(6), Some((2, 0)));
+
+    for map in [bounded, mirrored].iter() {
+        for count in map.rank_slopes(30, 5, Objective::FewestTrees) {
+            assert_eq!(
+                count.trees,
+                map.run(count.slope.dx, count.slope.dy).trees,
+                "{:?} {:?}",
+                map.edges(),
+                count.slope
+            );
+        }
+    }
+}
+
+#[test]
+fn render() {
+    let map = Map::parse(EXAMPLE);
+    let slopes = [Slope { dx: 3, dy: 1 }, Slope { dx: 1, dy: 2 }];
+
+    let ansi = render::ansi(&map, &slopes);
+    let plain: String = ansi
+        .split("\x1b[")
+        .enumerate()
+        .map(|(i, piece)| {
+            if i == 0 {
+                piece
+            } else {
+                &piece[piece.find('m').unwrap() + 1..]
+            }
+        })
+        .collect();
+    let lines: Vec<&str> = plain.lines().collect();
+
+    // The first slope reaches x = 30 on the last row, so the map is drawn three times over.
+    assert_eq!(lines[0], "O.##.........##.........##.......");
+    assert_eq!(lines[2], ".X....X..#..#....#..#..#....#..#.");
+    assert_eq!(lines[10], ".#..#O..#.#.#..#...#.#.#..#...X.#");
+    assert_eq!(lines[11], "O/X right 3, down 1: 7 trees");
+    assert_eq!(lines[12], "O/X right 1, down 2: 2 trees");
+    assert!(ansi.starts_with("\x1b[1;31mO\x1b[0m"));
+
+    let svg = render::svg(&map, &slopes);
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches(">X</text>").count(), 7 + 2);
+}
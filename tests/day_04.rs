@@ -0,0 +1,310 @@
+use std::convert::TryFrom;
+
+use aoc_2020::{day_04::*, schema::Schema};
+
+#[test]
+fn real_input() {
+    let input = std::fs::read_to_string("input/day-04.txt").unwrap();
+
+    assert_eq!(part1(&input), 206);
+    assert_eq!(part2(&input), 123);
+}
+
+#[test]
+fn passport() {
+    let passport = Passport::try_from(
+        "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f",
+    )
+    .unwrap();
+    assert_eq!(
+        passport,
+        Passport {
+            birth_year: 1980,
+            issue_year: 2012,
+            expiration_year: 2030,
+            height: Height::In(74),
+            hair_color: Rgb {
+                r: 0x62,
+                g: 0x3a,
+                b: 0x2f
+            },
+            eye_color: EyeColor::Green,
+            passport_id: "087499704".to_string(),
+            country_id: None,
+        }
+    );
+
+    let error = |record| Passport::try_from(record).unwrap_err();
+    assert_eq!(
+        error("eyr:2029 ecl:blu cid:129 byr:1989 iyr:2014 pid:896056539 hcl:#a97842"),
+        PassportError::MissingField("hgt")
+    );
+    assert_eq!(
+        error("hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 byr:2007"),
+        PassportError::MissingField("pid")
+    );
+    assert_eq!(
+        error("hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 pid:3556412378 byr:2007"),
+        PassportError::InvalidValue {
+            field: "byr",
+            value: "2007".to_string()
+        }
+    );
+    assert_eq!(
+        error("byr:1980 byr:1981"),
+        PassportError::Warning(Warning::DuplicateField("byr"))
+    );
+    assert_eq!(
+        error("byr:1980 name:Ann"),
+        PassportError::Warning(Warning::UnknownField("name".to_string()))
+    );
+
+    assert_eq!(Height::parse("190cm"), Some(Height::Cm(190)));
+    assert_eq!(Height::parse("190"), None);
+    assert_eq!(Rgb::parse("#123abz"), None);
+    assert_eq!(Rgb::parse("#123ABC"), None);
+    assert_eq!(EyeColor::parse("hzl"), Some(EyeColor::Hazel));
+    assert_eq!(EyeColor::parse("wat"), None);
+}
+
+#[test]
+fn report() {
+    let input = "\
+ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:58in
+";
+    let report = aoc_2020::day_04::report(input, &Policy::default()).unwrap();
+
+    assert_eq!(report.valid, 2);
+    assert_eq!(
+        report.rejections,
+        vec![
+            Rejection {
+                record: 2,
+                line: 4,
+                errors: vec![PassportError::MissingField("hgt")],
+            },
+            Rejection {
+                record: 4,
+                line: 12,
+                errors: vec![
+                    PassportError::MissingField("byr"),
+                    PassportError::InvalidValue {
+                        field: "hgt",
+                        value: "58in".to_string()
+                    }
+                ],
+            },
+        ]
+    );
+    assert_eq!(
+        report.to_string(),
+        "\
+record 2 (line 4): missing hgt
+record 4 (line 12): missing byr, invalid hgt \"58in\"
+2 valid, 2 rejected
+1 rejected for hgt
+1 rejected for missing byr
+1 rejected for missing hgt
+"
+    );
+
+    // Blank records are skipped, but still counted towards record and line numbers.
+    let report = aoc_2020::day_04::report("\n\n\n\nbyr:1937\n", &Policy::default()).unwrap();
+    assert_eq!(report.rejections[0].record, 3);
+    assert_eq!(report.rejections[0].line, 5);
+}
+
+#[test]
+fn policy() {
+    let record = "byr:1980 iyr:2012 eyr:2030 hgt:74in hcl:#623a2f ecl:grn pid:087499704 \
+                  byr:1990 name:Ann oops :x";
+    let warnings = vec![
+        Warning::DuplicateField("byr"),
+        Warning::UnknownField("name".to_string()),
+        Warning::MalformedToken("oops".to_string()),
+        Warning::MalformedToken(":x".to_string()),
+    ];
+
+    let checked = Passport::check(record, &Policy::default()).unwrap();
+    assert_eq!(checked.warnings, warnings);
+    assert_eq!(
+        checked.passport,
+        Err(vec![
+            PassportError::Warning(warnings[0].clone()),
+            PassportError::Warning(warnings[1].clone()),
+        ])
+    );
+
+    let lenient = Policy {
+        unknown_field: Treatment::Ignore,
+        duplicate_field: Treatment::Ignore,
+        malformed_token: Treatment::Ignore,
+    };
+    let checked = Passport::check(record, &lenient).unwrap();
+    assert_eq!(checked.warnings, warnings);
+    // The first of the repeated fields is the one used.
+    assert_eq!(checked.passport.unwrap().birth_year, 1980);
+
+    let strict = Policy {
+        malformed_token: Treatment::Error,
+        ..lenient
+    };
+    assert_eq!(Passport::check(record, &strict), Err(warnings[2].clone()));
+
+    let input = format!("pid:1\n\n{}\n", record);
+    let report = aoc_2020::day_04::report(&input, &lenient).unwrap();
+    assert_eq!(report.valid, 1);
+    assert_eq!(report.ignored.len(), 4);
+    assert_eq!(
+        report.ignored[3].to_string(),
+        "record 2 (line 3): malformed token \":x\""
+    );
+    assert_eq!(
+        aoc_2020::day_04::report(&input, &strict)
+            .unwrap_err()
+            .to_string(),
+        "record 2 (line 3): malformed token \"oops\""
+    );
+}
+
+#[test]
+fn fixtures() {
+    let valid = std::fs::read_to_string("input/day-04.valid.txt").unwrap();
+    let invalid = std::fs::read_to_string("input/day-04.invalid.txt").unwrap();
+
+    for record in valid.split("\n\n") {
+        assert!(Passport::try_from(record).is_ok(), "{}", record);
+        assert!(STRICT.validate(record), "{}", record);
+    }
+    for record in invalid.split("\n\n") {
+        assert!(Passport::try_from(record).is_err(), "{}", record);
+        assert!(!STRICT.validate(record), "{}", record);
+    }
+}
+
+#[test]
+fn field_edge_cases() {
+    let valid = "byr:1980 iyr:2012 eyr:2030 hgt:74in hcl:#623a2f ecl:grn pid:087499704";
+    // Each replacement is only just wrong.
+    let cases = [
+        ("byr:1980", "byr:+1980"),
+        ("iyr:2012", "iyr:02012"),
+        ("eyr:2030", "eyr:abc"),
+        ("hgt:74in", "hgt:x74iny"),
+        ("hcl:#623a2f", "hcl:#623a2fde"),
+        ("ecl:grn", "ecl:grnn"),
+        ("pid:087499704", "pid:0087499704"),
+    ];
+
+    assert!(Passport::try_from(valid).is_ok());
+    assert!(STRICT.validate(valid));
+    for (field, replacement) in cases.iter() {
+        let record = valid.replace(field, replacement);
+        let (key, value) = replacement.split_once(':').unwrap();
+
+        assert_eq!(
+            Passport::try_from(record.as_str()),
+            Err(PassportError::InvalidValue {
+                field: key,
+                value: value.to_string()
+            })
+        );
+        assert!(!STRICT.validate(&record), "{}", record);
+    }
+
+    // Any country ID is fine, or none at all.
+    assert!(Passport::try_from(format!("{} cid:+?", valid).as_str()).is_ok());
+    assert!(STRICT.validate(&format!("{} cid:+?", valid)));
+}
+
+#[test]
+fn passports_agree_with_schemas() {
+    let input = std::fs::read_to_string("input/day-04.txt").unwrap();
+
+    assert_eq!(part1(&input), count_valid(&input, &LAX));
+    assert_eq!(part2(&input), count_valid(&input, &STRICT));
+
+    // Part 1 only asks whether the fields are there, so a repeated one doesn't matter.
+    let repeated = "byr:1 iyr:1 eyr:1 hgt:1 hcl:1 ecl:1 pid:1 byr:2";
+    assert_eq!(part1(repeated), 1);
+    assert_eq!(count_valid(repeated, &LAX), 1);
+    assert_eq!(part1("byr:1 iyr:1 eyr:1 hgt:1 hcl:1 ecl:1 cid:1 cid:2"), 0);
+
+    // Nor does it in part 2, as long as every value is valid.
+    let repeated = "byr:1980 iyr:2012 eyr:2030 hgt:74in hcl:#623a2f ecl:grn pid:087499704 byr:1981";
+    assert_eq!(part2(repeated), 1);
+    assert_eq!(count_valid(repeated, &STRICT), 1);
+    let invalid = repeated.replace("byr:1981", "byr:2020");
+    assert_eq!(part2(&invalid), 0);
+    assert_eq!(count_valid(&invalid, &STRICT), 0);
+}
+
+#[test]
+fn schema() {
+    let schema = Schema::from_toml(
+        r#"
+        [fields.name]
+        required = true
+        check = { type = "regex", pattern = "[a-z]+" }
+
+        [fields.size]
+        required = true
+        check = { type = "unit", units = { kb = [1, 1024], mb = [1, 10] } }
+
+        [fields.kind]
+        required = false
+        check = { type = "enum", values = ["file", "dir"] }
+
+        [fields.mode]
+        required = false
+        check = { type = "int", min = 0, max = 777 }
+        "#,
+    )
+    .unwrap();
+
+    assert!(schema.validate("name:notes size:12kb"));
+    assert!(schema.validate("kind:dir\nname:src size:2mb mode:755"));
+    assert!(!schema.validate("name:notes"));
+    assert!(!schema.validate("name:Notes size:12kb"));
+    assert!(!schema.validate("name:notes size:11mb"));
+    assert!(!schema.validate("name:notes size:12gb"));
+    assert!(!schema.validate("name:notes size:12kb kind:link"));
+    assert!(!schema.validate("name:notes size:12kb mode:rwx"));
+    assert!(!schema.validate("name:notes size:+12kb"));
+    assert!(!schema.validate("name:notes size:12kb mode:+755"));
+    assert!(!schema.validate("name:notes size:12kb owner:me"));
+
+    assert!(schema.accepts("size", "10mb"));
+    assert!(!schema.accepts("size", "10"));
+    assert!(!schema.accepts("owner", "me"));
+}
+
+#[test]
+fn invalid_schemas() {
+    let error = |source: &str| Schema::from_toml(source).unwrap_err().to_string();
+
+    assert!(
+        error("[fields.a]\ncheck = { type = \"int\", min = 1, max = 2 }")
+            .contains("missing field `required`")
+    );
+    assert!(
+        error("[fields.a]\nrequired = true\ncheck = { type = \"range\" }")
+            .contains("unknown variant `range`")
+    );
+    assert!(
+        error("[fields.a]\nrequired = true\ncheck = { type = \"regex\", pattern = \"(\" }")
+            .contains("regex parse error")
+    );
+}
@@ -0,0 +1,215 @@
+use aoc_2020::day_05::{seat_map::*, *};
+
+use proptest::prelude::*;
+
+#[test]
+fn real_input() {
+    let input = std::fs::read_to_string("input/day-05.txt").unwrap();
+
+    assert_eq!(part1(&input), 980);
+    assert_eq!(part2(&input), Some(607));
+}
+
+#[test]
+fn decode() {
+    let layout = Layout::STANDARD;
+    for (pass, row, col, id) in [
+        ("FBFBBFFRLR", 44, 5, 357),
+        ("BFFFBBFRRR", 70, 7, 567),
+        ("FFFBBBFRRR", 14, 7, 119),
+        ("BBFFBBFRLL", 102, 4, 820),
+    ]
+    .iter()
+    {
+        let seat = layout.decode(pass).unwrap();
+        assert_eq!(
+            seat,
+            Seat {
+                row: *row,
+                col: *col
+            }
+        );
+        assert_eq!(layout.seat_id(seat), *id);
+    }
+
+    assert_eq!(
+        layout.decode("FBFBBFFRL"),
+        Err(DecodeError::Length {
+            expected: 10,
+            found: 9
+        })
+    );
+    // Row and column letters only count in their own half.
+    assert_eq!(
+        layout.decode("FBFBBFRRLR"),
+        Err(DecodeError::InvalidChar {
+            position: 7,
+            found: 'R'
+        })
+    );
+    assert_eq!(
+        layout.decode("FBFBBFFRLF").unwrap_err().to_string(),
+        "unexpected 'F' at position 10"
+    );
+}
+
+#[test]
+fn other_layouts() {
+    let small = Layout::new(2, 3);
+    assert_eq!((small.rows(), small.cols()), (4, 8));
+    assert_eq!(small.decode("BFRRR"), Ok(Seat { row: 2, col: 7 }));
+    assert_eq!(highest_seat_id("FBLRL\nBFRRR\n", small), Some(23));
+    assert_eq!(missing_seat_id("FBLRL\nFBLLL\n", small), Some(9));
+
+    let single_file = Layout::new(4, 0);
+    assert_eq!(single_file.seat_id(single_file.decode("BBFB").unwrap()), 13);
+
+    let widest = Layout::new(16, 16);
+    let pass = format!("{}{}", "B".repeat(16), "R".repeat(16));
+    assert_eq!(widest.seat_id(widest.decode(&pass).unwrap()), u32::MAX);
+    // Only the passes are kept, not a seat map of all 2^32 seats.
+    let passes = format!(
+        "{}\n{}\n",
+        widest.encode_id(u32::MAX).unwrap(),
+        widest.encode_id(u32::MAX - 2).unwrap()
+    );
+    assert_eq!(missing_seat_id(&passes, widest), Some(u32::MAX - 1));
+    let tallest = Layout::new(32, 0);
+    assert_eq!(tallest.decode(&"B".repeat(32)).unwrap().row, u32::MAX);
+}
+
+#[test]
+#[should_panic(expected = "make seat IDs too big")]
+fn too_many_bits() {
+    Layout::new(30, 3);
+}
+
+#[test]
+#[should_panic(expected = "make seat IDs too big")]
+fn overflowing_bits() {
+    Layout::new(u32::MAX, 1);
+}
+
+#[test]
+fn encode() {
+    let layout = Layout::STANDARD;
+    assert_eq!(
+        layout.encode(Seat { row: 44, col: 5 }),
+        Ok("FBFBBFFRLR".to_string())
+    );
+    assert_eq!(layout.encode_id(820), Ok("BBFFBBFRLL".to_string()));
+    assert_eq!(layout.seat(567), Ok(Seat { row: 70, col: 7 }));
+
+    // Every seat on the standard aircraft.
+    for id in 0..1024 {
+        let pass = layout.encode_id(id).unwrap();
+        assert_eq!(layout.seat_id(layout.decode(&pass).unwrap()), id);
+    }
+
+    assert_eq!(
+        layout.encode(Seat { row: 128, col: 0 }),
+        Err(EncodeError::Row {
+            row: 128,
+            rows: 128
+        })
+    );
+    assert_eq!(
+        layout.encode(Seat { row: 0, col: 8 }),
+        Err(EncodeError::Col { col: 8, cols: 8 })
+    );
+    assert_eq!(layout.checked_seat_id(Seat { row: 127, col: 7 }), Ok(1023));
+    assert_eq!(
+        layout.checked_seat_id(Seat { row: 0, col: 8 }),
+        Err(EncodeError::Col { col: 8, cols: 8 })
+    );
+    assert_eq!(
+        layout.encode_id(1024).unwrap_err().to_string(),
+        "seat ID 1024 is past the last of 1024 seats"
+    );
+    assert_eq!(Layout::new(32, 0).encode_id(u32::MAX), Ok("B".repeat(32)));
+}
+
+#[test]
+fn seat_map() {
+    let layout = Layout::new(2, 2);
+    let map = SeatMap::from_ids(layout, vec![1, 3, 4, 5, 9, 10]);
+
+    assert_eq!(map.taken_count(), 6);
+    assert!(map.is_taken(Seat { row: 1, col: 1 }));
+    assert!(!map.is_taken(Seat { row: 1, col: 2 }));
+    assert_eq!(
+        map.free_ids().collect::<Vec<_>>(),
+        vec![0, 2, 6, 7, 8, 11, 12, 13, 14, 15]
+    );
+    assert_eq!(map.free_between_taken().collect::<Vec<_>>(), vec![2]);
+    assert_eq!(map.missing_front(), 0..1);
+    assert_eq!(map.missing_back(), 11..16);
+    assert_eq!(map.longest_free_run(), Some(FreeRun { row: 3, cols: 0..4 }));
+    assert_eq!(map.render(), "0 .#.#\n1 ##..\n2 .##.\n3 ....\n");
+
+    let mut map = SeatMap::new(layout);
+    assert_eq!(map.missing_front(), 0..16);
+    assert_eq!(map.missing_back(), 16..16);
+    assert_eq!(map.longest_free_run(), Some(FreeRun { row: 0, cols: 0..4 }));
+
+    for id in 0..16 {
+        map.take(layout.seat(id).unwrap());
+    }
+    assert_eq!(map.free_ids().count(), 0);
+    assert_eq!(map.longest_free_run(), None);
+    assert_eq!((map.missing_front(), map.missing_back()), (0..0, 16..16));
+}
+
+#[test]
+#[should_panic(expected = "column 8 is past the last of 8 columns")]
+fn take_past_the_last_column() {
+    // Seat ID 8, so without the check this would take row 1, column 0.
+    SeatMap::new(Layout::STANDARD).take(Seat { row: 0, col: 8 });
+}
+
+#[test]
+#[should_panic(expected = "row 4 is past the last of 4 rows")]
+fn is_taken_past_the_last_row() {
+    SeatMap::new(Layout::new(2, 2)).is_taken(Seat { row: 4, col: 0 });
+}
+
+#[test]
+fn seat_map_real_input() {
+    let input = std::fs::read_to_string("input/day-05.txt").unwrap();
+    let layout = Layout::STANDARD;
+    let map = SeatMap::from_ids(layout, seat_ids(&input, layout));
+
+    assert_eq!(map.free_between_taken().collect::<Vec<_>>(), vec![607]);
+    assert_eq!(map.missing_back().start, 981);
+    assert_eq!(
+        map.free_ids().count(),
+        map.missing_front().len() + map.missing_back().len() + 1
+    );
+}
+
+// Any layout of up to 32 bits, with a seat on it.
+fn layout_and_seat() -> impl Strategy<Value = (Layout, Seat)> {
+    (0u32..=16, 0u32..=16).prop_flat_map(|(row_bits, col_bits)| {
+        let layout = Layout::new(row_bits, col_bits);
+        (
+            Just(layout),
+            (0..layout.rows(), 0..layout.cols()).prop_map(|(row, col)| Seat {
+                row: row as u32,
+                col: col as u32,
+            }),
+        )
+    })
+}
+
+proptest! {
+    #[test]
+    fn encode_then_decode((layout, seat) in layout_and_seat()) {
+        let pass = layout.encode(seat).unwrap();
+        prop_assert_eq!(pass.len() as u32, layout.row_bits() + layout.col_bits());
+        prop_assert_eq!(layout.decode(&pass), Ok(seat));
+
+        let id = layout.seat_id(seat);
+        prop_assert_eq!(layout.seat(id), Ok(seat));
+        prop_assert_eq!(layout.encode_id(id), Ok(pass));
+    }
+}
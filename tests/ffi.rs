@@ -0,0 +1,61 @@
+#![cfg(feature = "ffi")]
+
+use std::{env, path::PathBuf, process::Command};
+
+// The crate isn't built as a static library by default, so build one into its own target
+// directory, which also keeps it from waiting on the lock of the one running the tests.
+fn build_static_lib() -> PathBuf {
+    let target_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "rustc",
+            "--lib",
+            "--features",
+            "ffi",
+            "--crate-type",
+            "staticlib",
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("Couldn't run cargo");
+    assert!(status.success());
+
+    target_dir.join("debug")
+}
+
+#[test]
+fn c_example() {
+    let target_dir = build_static_lib();
+    let example = target_dir.join("aoc-2020-c-example");
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg("examples/c/solve.c")
+        .arg("-Iinclude")
+        .arg("-o")
+        .arg(&example)
+        .arg(target_dir.join("libaoc_2020.a"))
+        .args(["-lpthread", "-ldl", "-lm"])
+        .status()
+        .expect("Couldn't run the C compiler");
+    assert!(status.success());
+
+    let output = Command::new(&example)
+        .args(["1", "input/day-01.txt"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Part 1: 1006875\nPart 2: 165026160\n"
+    );
+
+    let output = Command::new(&example)
+        .args(["8", "input/day-08.txt"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Part 1: 1671\nPart 2 failed (5): not yet implemented\n"
+    );
+}
@@ -0,0 +1,141 @@
+use aoc_2020::{lint::lint, registry::DAYS};
+
+// Every problem, formatted with its line number.
+fn problems(day: u32, input: &str) -> Vec<String> {
+    lint(day, input)
+        .unwrap()
+        .iter()
+        .map(|problem| problem.to_string())
+        .collect()
+}
+
+#[test]
+fn real_inputs_are_clean() {
+    for day in DAYS {
+        let input = std::fs::read_to_string(day.input_path()).unwrap();
+        assert_eq!(
+            problems(day.number, &input),
+            Vec::<String>::new(),
+            "day {}",
+            day.number
+        );
+    }
+}
+
+#[test]
+fn unknown_day() {
+    assert!(lint(26, "1\n").is_none());
+}
+
+#[test]
+fn whole_file() {
+    assert_eq!(problems(1, ""), vec!["file is empty"]);
+    assert_eq!(
+        problems(1, "1\r\n2\n"),
+        vec!["line 1: line ends with CRLF instead of LF"]
+    );
+    assert_eq!(problems(1, "1\n2 \n"), vec!["line 2: trailing whitespace"]);
+    assert_eq!(
+        problems(1, "1\n\n2\n"),
+        vec!["line 2: unexpected blank line"]
+    );
+
+    // A missing final line ending is only mentioned when that line is broken too.
+    assert!(problems(1, "1\n2").is_empty());
+    assert_eq!(
+        problems(1, "1\n2x"),
+        vec![
+            "line 2: expected an integer, found \"2x\" \
+             (the last line has no line ending, so the file may be truncated)"
+        ]
+    );
+}
+
+#[test]
+fn groups() {
+    assert!(problems(6, "ab\nc\n\nd\n").is_empty());
+    assert_eq!(
+        problems(6, "\nab\n"),
+        vec!["line 1: blank line does not separate two groups"]
+    );
+    assert_eq!(
+        problems(6, "ab\n\n\ncd\n"),
+        vec!["line 3: blank line does not separate two groups"]
+    );
+    assert_eq!(
+        problems(6, "ab\n\n"),
+        vec!["line 2: blank line at the end of the file"]
+    );
+    assert_eq!(
+        problems(6, "ab\nC\n"),
+        vec!["line 2: unexpected 'C', expected only 'a' to 'z'"]
+    );
+}
+
+#[test]
+fn map_rows() {
+    assert_eq!(
+        problems(3, "..#\n.#\n#.x\n#..#\n"),
+        vec![
+            "line 2: row is 2 wide, but the first row is 3",
+            "line 3: unexpected 'x', expected only '.' and '#'",
+            "line 4: row is 4 wide, but the first row is 3",
+        ]
+    );
+}
+
+#[test]
+fn passport_fields() {
+    assert!(problems(4, "byr:1937 hcl:#fffffd\ncid:147\n\npid:1\n").is_empty());
+    assert_eq!(
+        problems(4, "byr:19x7 foo:1 pid\n"),
+        vec![
+            "line 1: byr should be a year, found \"19x7\"",
+            "line 1: unknown field \"foo\"",
+            "line 1: expected `<key>:<value>`, found \"pid\"",
+        ]
+    );
+}
+
+#[test]
+fn password_entries() {
+    // Anything `PasswordEntry::parse` accepts, including non-ASCII letters and any single
+    // character as the letter.
+    assert!(problems(2, "1-3 a: abcde\n1-3 é: aéb\n2-9 Z: ZZ9Z\n1-1 -: a-b c\n").is_empty());
+
+    assert_eq!(
+        problems(2, "1-3 a abc\n1-x a: abc\n1-3 ab: abc\n1-3 a:\n"),
+        vec![
+            "line 1: expected `<lower>-<upper> <letter>: <password>`",
+            "line 2: invalid bound \"x\"",
+            "line 3: expected a single letter, found \"ab\"",
+            "line 4: expected `<lower>-<upper> <letter>: <password>`",
+        ]
+    );
+}
+
+#[test]
+fn boarding_passes() {
+    assert_eq!(
+        problems(5, "FBFBBFFRL\nFBFBBFRRLX\n"),
+        vec![
+            "line 1: expected 10 characters, found 9",
+            "line 2: unexpected 'R' in the row, expected 'F' or 'B'",
+            "line 2: unexpected 'X' in the column, expected 'L' or 'R'",
+        ]
+    );
+}
+
+#[test]
+fn day_9_length() {
+    let numbers = |n: u32| (1..=n).map(|i| format!("{}\n", i)).collect::<String>();
+
+    assert!(problems(9, &numbers(26)).is_empty());
+    assert_eq!(
+        problems(9, &format!("{}x\n", numbers(24))),
+        vec![
+            "expected more than 25 numbers, found 25",
+            "line 25: expected an integer, found \"x\"",
+        ]
+    );
+}
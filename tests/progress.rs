@@ -0,0 +1,148 @@
+use aoc_2020::{
+    answers::KnownAnswers,
+    progress::*,
+    registry::{Answer, SolveError},
+};
+
+fn date(s: &str) -> Date {
+    s.parse().unwrap()
+}
+
+#[test]
+fn days_since_epoch() {
+    for (days, expected) in [
+        (0, "1970-01-01"),
+        (-1, "1969-12-31"),
+        (11016, "2000-02-29"),
+        (18597, "2020-12-01"),
+        (-25508, "1900-03-01"),
+        (47540, "2100-02-28"),
+    ]
+    .iter()
+    {
+        assert_eq!(Date::from_days_since_epoch(*days).to_string(), *expected);
+    }
+}
+
+#[test]
+fn dates() {
+    assert_eq!(
+        date("2020-12-01"),
+        Date {
+            year: 2020,
+            month: 12,
+            day: 1
+        }
+    );
+    assert_eq!(
+        "2020-12".parse::<Date>(),
+        Err("expected a YYYY-MM-DD date, found \"2020-12\"".to_string())
+    );
+    assert_eq!(
+        "2020-xx-01".parse::<Date>(),
+        Err("invalid date \"2020-xx-01\"".to_string())
+    );
+}
+
+#[test]
+fn stage_from_result() {
+    let known = KnownAnswers::parse("3 1 299\n").unwrap();
+    let stage = |part, result| Stage::from_result(3, part, &result, &known);
+
+    assert_eq!(stage(1, Ok(Answer::Number(299))), Stage::Verified);
+    assert_eq!(stage(1, Ok(Answer::Number(7))), Stage::Wrong);
+    assert_eq!(stage(2, Ok(Answer::Number(7))), Stage::Solved);
+    assert_eq!(stage(1, Ok(Answer::NoSolution)), Stage::Implemented);
+    assert_eq!(
+        stage(1, Err(SolveError::Panicked("bad input".to_string()))),
+        Stage::Implemented
+    );
+    assert_eq!(
+        stage(1, Err(SolveError::Unimplemented)),
+        Stage::Unimplemented
+    );
+}
+
+#[test]
+fn record() {
+    let mut progress = Progress::default();
+    let first_solved = |progress: &Progress| progress.get(3, 1).unwrap().first_solved;
+
+    progress.record(3, 1, Stage::Implemented, date("2020-12-03"));
+    assert_eq!(first_solved(&progress), None);
+
+    // A wrong answer isn't solved, so it doesn't get a date.
+    progress.record(3, 1, Stage::Wrong, date("2020-12-04"));
+    assert_eq!(progress.get(3, 1).unwrap().stage, Stage::Wrong);
+    assert_eq!(first_solved(&progress), None);
+
+    progress.record(3, 1, Stage::Verified, date("2020-12-05"));
+    assert_eq!(first_solved(&progress), Some(date("2020-12-05")));
+    assert_eq!(progress.stars(), 1);
+
+    // The first date is kept through regressions and later solves.
+    progress.record(3, 1, Stage::Wrong, date("2020-12-06"));
+    progress.record(3, 1, Stage::Solved, date("2020-12-07"));
+    assert_eq!(first_solved(&progress), Some(date("2020-12-05")));
+    assert_eq!(progress.stars(), 0);
+
+    progress.record(1, 2, Stage::Solved, date("2020-12-08"));
+    assert_eq!(
+        progress.get(1, 2).unwrap().first_solved,
+        Some(date("2020-12-08"))
+    );
+    assert_eq!(progress.get(2, 1), None);
+}
+
+#[test]
+fn file_round_trip() {
+    let file = "\
+version 1
+1 1 verified 2020-12-01
+1 2 solved 2020-12-02
+3 1 wrong -
+8 2 unimplemented -
+";
+    let progress: Progress = file.parse().unwrap();
+
+    assert_eq!(
+        progress.get(3, 1),
+        Some(Record {
+            stage: Stage::Wrong,
+            first_solved: None
+        })
+    );
+    assert_eq!(progress.to_string(), file);
+    assert!(progress.calendar().contains(" 1 *+    2 ..    3 x."));
+}
+
+#[test]
+fn file_errors() {
+    let error = |file: &str| file.parse::<Progress>().unwrap_err();
+
+    assert!("".parse::<Progress>().unwrap().get(1, 1).is_none());
+    assert_eq!(
+        error("version 0\n"),
+        "unsupported progress file \"version 0\", expected `version 1`"
+    );
+    assert_eq!(
+        error("version 1\n1 1 solved\n"),
+        "line 2: expected `<day> <part> <stage> <first solved>`, found \"1 1 solved\""
+    );
+    assert_eq!(
+        error("version 1\n1 1 verified -\nx 1 solved -\n"),
+        "line 3: invalid day \"x\""
+    );
+    assert_eq!(
+        error("version 1\n1 y solved -\n"),
+        "line 2: invalid part \"y\""
+    );
+    assert_eq!(
+        error("version 1\n1 1 done -\n"),
+        "line 2: unknown stage \"done\""
+    );
+    assert_eq!(
+        error("version 1\n1 1 solved 2020/12/01\n"),
+        "line 2: expected a YYYY-MM-DD date, found \"2020/12/01\""
+    );
+}
@@ -0,0 +1,24 @@
+use aoc_2020::registry::{self, PartStatus, SolveError};
+
+#[test]
+fn status() {
+    let day = |number| registry::day(number).unwrap();
+
+    assert_eq!(day(1).status(1), Some(PartStatus::Implemented));
+    assert_eq!(day(8).status(2), Some(PartStatus::Unimplemented));
+    assert_eq!(day(8).status(3), None);
+    assert!(registry::day(26).is_none());
+}
+
+#[test]
+fn solve() {
+    assert_eq!(registry::solve(8, 2, ""), Err(SolveError::Unimplemented));
+    assert_eq!(registry::solve(1, 3, ""), Err(SolveError::UnknownPart(3)));
+    assert_eq!(registry::solve(26, 1, ""), Err(SolveError::UnknownDay(26)));
+    assert_eq!(
+        registry::solve(1, 1, "1721\n979\n366\n299\n675\n1456\n")
+            .unwrap()
+            .to_string(),
+        "514579"
+    );
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "server")]
+
+use std::time::Duration;
+
+use {
+    aoc_2020::server::{router, Config},
+    axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    },
+    http_body_util::BodyExt,
+    serde_json::{json, Value},
+    tower::ServiceExt,
+};
+
+async fn send(config: Config, request: Request<Body>) -> (StatusCode, Value) {
+    let response = router(config).oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn post(uri: &str, body: impl Into<Body>) -> Request<Body> {
+    Request::post(uri).body(body.into()).unwrap()
+}
+
+#[tokio::test]
+async fn list_days() {
+    let request = Request::get("/days").body(Body::empty()).unwrap();
+    let (status, body) = send(Config::default(), request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 10);
+    assert_eq!(
+        body[7],
+        json!({
+            "day": 8,
+            "parts": [
+                { "part": 1, "status": "implemented" },
+                { "part": 2, "status": "unimplemented" },
+            ],
+        })
+    );
+}
+
+#[tokio::test]
+async fn solve() {
+    let input = std::fs::read_to_string("input/day-01.txt").unwrap();
+    let (status, body) = send(Config::default(), post("/days/1/parts/2", input)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["answer"], 165026160);
+    assert!(body["elapsed_ms"].is_f64());
+}
+
+#[tokio::test]
+async fn solve_errors() {
+    let (status, _) = send(Config::default(), post("/days/25/parts/1", "")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(Config::default(), post("/days/1/parts/3", "")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(Config::default(), post("/days/8/parts/2", "")).await;
+    assert_eq!(status, StatusCode::NOT_IMPLEMENTED);
+
+    let (status, body) = send(Config::default(), post("/days/1/parts/1", "abc\n")).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["error"].as_str().unwrap().contains("panicked"));
+}
+
+#[tokio::test]
+async fn body_limit() {
+    let config = Config {
+        max_input_bytes: 16,
+        ..Config::default()
+    };
+    let (status, _) = send(config, post("/days/1/parts/1", "1\n".repeat(9))).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[test]
+fn timeout() {
+    // Nothing before 1000 sums to it, so `day_09::part2` searches for a range forever. The runtime
+    // would wait on that blocking thread when dropped, so shut it down without waiting.
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let config = Config {
+        timeout: Duration::from_millis(100),
+        ..Config::default()
+    };
+    let input: String = (1..=25)
+        .chain(Some(1000))
+        .map(|n| format!("{}\n", n))
+        .collect();
+
+    let (status, _) = runtime.block_on(send(config, post("/days/9/parts/2", input)));
+    assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+
+    runtime.shutdown_background();
+}